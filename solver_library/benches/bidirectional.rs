@@ -0,0 +1,26 @@
+// Compares the bidirectional search with the best-first search on hard puzzles
+// (see `common`):
+//
+//   cargo bench --bench bidirectional
+
+mod common;
+
+use ricochet_robots_solver::solver::{
+    bidirectional::BidirectionalSolver,
+    move_sequence::{MoveSequence, MoveSequenceLinkedList},
+    solver::Solver,
+};
+
+fn main() {
+    common::compare_with_best_first("bidirectional", |puzzle| {
+        BidirectionalSolver
+            .solve(
+                &puzzle.board,
+                puzzle.robot_positions,
+                MoveSequenceLinkedList::empty(),
+                puzzle.target_square.target(),
+            )
+            .unwrap()
+            .map(|solution| solution.moves().len())
+    });
+}
@@ -0,0 +1,103 @@
+// Hard puzzles shared by the benchmarks comparing other solvers with the
+// best-first search: random boards made of the standard tiles, with robots
+// placed at random and targets needing at least `MIN_MOVES` moves. Each puzzle
+// is drawn from its own seed, printed in the first column of the results, so
+// that `puzzle(seed)` gives the puzzle of any row back.
+
+use std::time::{Duration, Instant};
+
+use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
+use ricochet_robots_solver::solver::{
+    board::Board,
+    boards::BoardLayout,
+    move_sequence::{MoveSequence, MoveSequenceLinkedList},
+    robot_positions::RobotPositionsPacked,
+    solver::{BestFirstSolver, Solver},
+    wall_configuration::WallConfigurationTable,
+    Position, TargetSquare,
+};
+
+const PUZZLES: usize = 12;
+const MIN_MOVES: usize = 9;
+
+pub struct Puzzle {
+    pub seed: u64,
+    pub board: Board<WallConfigurationTable>,
+    pub robot_positions: RobotPositionsPacked,
+    pub target_square: TargetSquare,
+}
+
+pub fn puzzle(seed: u64) -> Puzzle {
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let board = BoardLayout::random(&mut rng).board();
+    let wall_configuration = WallConfigurationTable::new(board.get_wall_configuration())
+        .expect("standard boards fit in a table");
+    let board = board.with_wall_configuration(wall_configuration);
+    let free_squares: Vec<Position> = (0..board.get_height())
+        .flat_map(|row| (0..board.get_width()).map(move |col| Position::new(row, col)))
+        .filter(|position| !board.is_blocked(position))
+        .collect();
+    let positions: Vec<Position> = free_squares.choose_multiple(&mut rng, 4).cloned().collect();
+    let robot_positions =
+        RobotPositionsPacked::new(&positions, board.get_height(), board.get_width()).unwrap();
+    let target_square = board.get_targets().choose(&mut rng).unwrap().clone();
+    Puzzle {
+        seed,
+        board,
+        robot_positions,
+        target_square,
+    }
+}
+
+// The first `PUZZLES` hard puzzles, along with the length of their shortest
+// solutions and the time `BestFirstSolver` took to find them (it also tells
+// the easy puzzles apart)
+pub fn hard_puzzles() -> impl Iterator<Item = (Puzzle, usize, Duration)> {
+    (0..)
+        .map(puzzle)
+        .filter_map(|puzzle| {
+            let start = Instant::now();
+            let moves = BestFirstSolver::default()
+                .solve(
+                    &puzzle.board,
+                    puzzle.robot_positions,
+                    MoveSequenceLinkedList::empty(),
+                    puzzle.target_square.target(),
+                )
+                .unwrap()
+                .map(|solution| solution.moves().len())?;
+            let duration = start.elapsed();
+            (moves >= MIN_MOVES).then_some((puzzle, moves, duration))
+        })
+        .take(PUZZLES)
+}
+
+// Times `solve` on every hard puzzle, and prints it next to the best-first
+// search. `solve` has to find solutions as short as the best-first search.
+pub fn compare_with_best_first(name: &str, solve: impl Fn(&Puzzle) -> Option<usize>) {
+    println!(
+        "{:<8} {:<20} {:>5} {:>12} {:>14}",
+        "seed", "target", "moves", "best-first", name
+    );
+    let mut totals = [Duration::ZERO; 2];
+    for (puzzle, moves, best_first) in hard_puzzles() {
+        let start = Instant::now();
+        let other_moves = solve(&puzzle);
+        let other = start.elapsed();
+        assert_eq!(other_moves, Some(moves), "seed {}", puzzle.seed);
+        totals[0] += best_first;
+        totals[1] += other;
+        println!(
+            "{:<8} {:<20} {:>5} {:>12.1?} {:>14.1?}",
+            puzzle.seed,
+            format!("{:?}", puzzle.target_square.chip),
+            moves,
+            best_first,
+            other
+        );
+    }
+    println!(
+        "{:<8} {:<20} {:>5} {:>12.1?} {:>14.1?}",
+        "total", "", "", totals[0], totals[1]
+    );
+}
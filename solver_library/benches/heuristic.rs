@@ -0,0 +1,61 @@
+// Compares the number of positions explored by the search with and without the
+// A* heuristic, on every target of the board used by the web app:
+//
+//   cargo bench --bench heuristic
+
+use ricochet_robots_solver::solver::{
+    boards::BoardLayout,
+    move_sequence::{MoveSequence, MoveSequenceLinkedList},
+    robot_positions::RobotPositionsVec,
+    solver, Position,
+};
+
+fn main() {
+    let board = BoardLayout::default().board();
+    // initial positions of the web app
+    let robot_positions = RobotPositionsVec::new(vec![
+        Position::new(4, 3),
+        Position::new(4, 8),
+        Position::new(9, 4),
+        Position::new(10, 14),
+    ]);
+
+    println!(
+        "{:<24} {:>5} {:>12} {:>10} {:>12} {:>10}",
+        "target", "moves", "bfs explored", "bfs time", "a* explored", "a* time"
+    );
+    let mut totals = [0, 0];
+    for target_square in board.get_targets() {
+        let mut row = format!("{:<24}", format!("{:?}", target_square.chip));
+        let mut moves = vec![];
+        for (index, use_heuristic) in [false, true].into_iter().enumerate() {
+            let result = solver::search(
+                &board,
+                robot_positions.clone(),
+                MoveSequenceLinkedList::empty(),
+                target_square.target(),
+                use_heuristic,
+            )
+            .unwrap();
+            moves.push(result.solution.map(|solution| solution.moves().len()));
+            if index == 0 {
+                row += &format!(
+                    " {:>5}",
+                    moves[0].map_or("-".to_string(), |m| m.to_string())
+                );
+            }
+            row += &format!(
+                " {:>12} {:>10.1?}",
+                result.stats.explored_positions, result.stats.duration
+            );
+            totals[index] += result.stats.explored_positions;
+        }
+        println!("{}", row);
+        // the heuristic never makes the solutions longer
+        assert_eq!(moves[0], moves[1]);
+    }
+    println!(
+        "{:<24} {:>5} {:>12} {:>10} {:>12}",
+        "total", "", totals[0], "", totals[1]
+    );
+}
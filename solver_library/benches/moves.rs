@@ -0,0 +1,130 @@
+// Compares move generation using the slides precomputed by `Board` with move
+// generation recomputing the wall stops from the wall configuration, on the
+// board used by the web app:
+//
+//   cargo bench --bench moves
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
+use ricochet_robots_solver::solver::{
+    boards::BoardLayout,
+    robot_positions::{RobotPositions, RobotPositionsVec},
+    wall_configuration::WallConfiguration,
+    Position,
+};
+
+const ITERATIONS: usize = 200;
+
+// Move generation as it was done before the slides were precomputed
+fn get_valid_moves_without_slides<W: WallConfiguration, P: RobotPositions>(
+    walls: &W,
+    robot: usize,
+    robot_positions: &P,
+) -> Vec<Position> {
+    let position = robot_positions.get_robot_position(robot);
+    let up = [
+        walls.next_wall_up(&position),
+        robot_positions.next_robot_up(&position),
+        Some(0),
+    ]
+    .into_iter()
+    .flatten()
+    .max()
+    .map(|row| Position::new(row, position.col));
+    let down = [
+        walls.next_wall_down(&position),
+        robot_positions.next_robot_down(&position),
+        Some(walls.get_height() - 1),
+    ]
+    .into_iter()
+    .flatten()
+    .min()
+    .map(|row| Position::new(row, position.col));
+    let right = [
+        walls.next_wall_right(&position),
+        robot_positions.next_robot_right(&position),
+        Some(walls.get_width() - 1),
+    ]
+    .into_iter()
+    .flatten()
+    .min()
+    .map(|col| Position::new(position.row, col));
+    let left = [
+        walls.next_wall_left(&position),
+        robot_positions.next_robot_left(&position),
+        Some(0),
+    ]
+    .into_iter()
+    .flatten()
+    .max()
+    .map(|col| Position::new(position.row, col));
+    [up, down, right, left]
+        .into_iter()
+        .flatten()
+        .filter(|next_position| *next_position != position)
+        .collect()
+}
+
+fn main() {
+    let board = BoardLayout::default().board();
+    let walls = BoardLayout::default().wall_configuration();
+    let free_squares: Vec<Position> = (0..board.get_height())
+        .flat_map(|row| (0..board.get_width()).map(move |col| Position::new(row, col)))
+        .filter(|position| !board.is_blocked(position))
+        .collect();
+    let mut rng = SmallRng::seed_from_u64(0);
+    let robot_positions: Vec<RobotPositionsVec> = (0..1000)
+        .map(|_| {
+            RobotPositionsVec::new(free_squares.choose_multiple(&mut rng, 4).cloned().collect())
+        })
+        .collect();
+
+    // both ways have to find the same moves
+    for positions in &robot_positions {
+        for robot in 0..4 {
+            let moves: Vec<Position> = board
+                .get_valid_moves_for_robot(robot, positions)
+                .into_iter()
+                .map(|(_, position)| position)
+                .collect();
+            assert_eq!(
+                moves,
+                get_valid_moves_without_slides(&walls, robot, positions)
+            );
+        }
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for positions in &robot_positions {
+            for robot in 0..4 {
+                black_box(get_valid_moves_without_slides(&walls, robot, positions));
+            }
+        }
+    }
+    let without_slides = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for positions in &robot_positions {
+            for robot in 0..4 {
+                black_box(board.get_valid_moves_for_robot(robot, positions));
+            }
+        }
+    }
+    let with_slides = start.elapsed();
+
+    let calls = ITERATIONS * robot_positions.len() * 4;
+    println!(
+        "without slides: {:>10.1?} ({:.1?} per call)",
+        without_slides,
+        without_slides / calls as u32
+    );
+    println!(
+        "with slides:    {:>10.1?} ({:.1?} per call)",
+        with_slides,
+        with_slides / calls as u32
+    );
+}
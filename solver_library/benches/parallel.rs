@@ -0,0 +1,22 @@
+// Compares the parallel solver with the best-first search on the same hard
+// puzzles as the `bidirectional` benchmark (see `common`):
+//
+//   cargo bench --features parallel --bench parallel
+
+mod common;
+
+use ricochet_robots_solver::solver::parallel::ParallelSolver;
+
+fn main() {
+    println!("{} threads", rayon::current_num_threads());
+    common::compare_with_best_first("parallel", |puzzle| {
+        ParallelSolver::default()
+            .solve(
+                &puzzle.board,
+                puzzle.robot_positions,
+                puzzle.target_square.target(),
+            )
+            .unwrap()
+            .map(|solution| solution.moves().len())
+    });
+}
@@ -0,0 +1,399 @@
+mod puzzle_file;
+
+use std::io::{IsTerminal, Read};
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::{rngs::SmallRng, SeedableRng};
+use ricochet_robots_solver::solver::{
+    analysis,
+    boards::BoardLayout,
+    generator::{self, GenerateOptions},
+    move_sequence::{Move, MoveSequence, MoveSequenceLinkedList},
+    progress::Progress,
+    puzzle::{Puzzle, PuzzleMetadata},
+    robot_positions::RobotPositions,
+    solver::{self, SearchLimit, SolveOptions, SolveOutcome},
+    Direction, Position,
+};
+
+// Same icons as the ones used by the web app
+const ROBOT_ICONS: [&str; 4] = ["🔴", "🔵", "🟢", "🟡"];
+
+const USAGE: &str = "\
+Usage: ricochet-robots [--format text|ascii|json] [--max-moves N] [--max-positions N] [FILE]
+       ricochet-robots --generate MOVES [--seed N] [--format text|ascii|json] [FILE]
+       ricochet-robots --analyze [--format text|ascii|json] [FILE]
+       ricochet-robots --distances ROBOT [--all-robots-move] [--max-moves N] [--format text|ascii|json] [FILE]
+
+Solves the puzzle described in FILE (or read from stdin if FILE is missing or
+`-`), and prints the shortest sequence of moves. While searching, a progress
+line is shown on stderr if it is a terminal.
+
+Options:
+  --format text   line-based description of the walls, robots and target (default)
+  --format ascii  ASCII art drawing of the board
+  --format json   puzzle exported by the web app
+  --max-moves N      only look for solutions of at most N moves
+  --max-positions N  give up after exploring N positions
+
+With --generate, prints instead a random puzzle (in the JSON format) whose
+shortest solutions have MOVES moves (a number, or a range like `6-8`). Its walls
+are the ones of FILE if given, or those of a random standard board otherwise.
+The same --seed always gives the same puzzle.
+
+With --analyze, solves the puzzle from every placement of its robots (with the
+same walls and target) and prints how many placements have solutions of each
+length, along with the placements with the longest ones. This is only practical
+with up to 3 robots on a 16x16 board.
+
+With --distances, prints the fewest moves for ROBOT (numbered from 0) to reach
+each square, the other robots staying where they are. With --all-robots-move,
+the other robots can move too, and only squares reached in at most --max-moves
+moves (5 by default) are shown.
+
+Exits with status 1 if the puzzle has no solution (or if no puzzle was
+generated), 2 if it is invalid, and 3 if the search gave up because of
+--max-moves or --max-positions.";
+
+struct Arguments {
+    format: String,
+    path: Option<String>,
+    max_moves: Option<usize>,
+    max_positions: Option<usize>,
+    // bounds of the number of moves of the generated puzzle
+    generate: Option<(usize, usize)>,
+    seed: Option<u64>,
+    analyze: bool,
+    distances: Option<usize>,
+    all_robots_move: bool,
+}
+
+fn parse_number(option: &str, value: Option<String>) -> Result<usize, String> {
+    let value = value.ok_or(format!("missing value for {}", option))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{}` for {}", value, option))
+}
+
+// `N`, or `MIN-MAX`
+fn parse_range(option: &str, value: Option<String>) -> Result<(usize, usize), String> {
+    let value = value.ok_or(format!("missing value for {}", option))?;
+    let parse = |bound: &str| {
+        bound
+            .parse()
+            .map_err(|_| format!("invalid value `{}` for {}", value, option))
+    };
+    match value.split_once('-') {
+        Some((min, max)) => Ok((parse(min)?, parse(max)?)),
+        None => parse(&value).map(|moves| (moves, moves)),
+    }
+}
+
+fn parse_arguments(mut args: impl Iterator<Item = String>) -> Result<Arguments, String> {
+    let mut arguments = Arguments {
+        format: "text".to_string(),
+        path: None,
+        max_moves: None,
+        max_positions: None,
+        generate: None,
+        seed: None,
+        analyze: false,
+        distances: None,
+        all_robots_move: false,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                arguments.format = args.next().ok_or("missing value for --format")?;
+            }
+            "--max-moves" => {
+                arguments.max_moves = Some(parse_number(&arg, args.next())?);
+            }
+            "--max-positions" => {
+                arguments.max_positions = Some(parse_number(&arg, args.next())?);
+            }
+            "--generate" => {
+                arguments.generate = Some(parse_range(&arg, args.next())?);
+            }
+            "--analyze" => arguments.analyze = true,
+            "--distances" => {
+                arguments.distances = Some(parse_number(&arg, args.next())?);
+            }
+            "--all-robots-move" => arguments.all_robots_move = true,
+            "--seed" => {
+                arguments.seed = Some(parse_number(&arg, args.next())? as u64);
+            }
+            _ if arguments.path.is_none() && (arg == "-" || !arg.starts_with('-')) => {
+                arguments.path = Some(arg);
+            }
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+    Ok(arguments)
+}
+
+fn read_input(path: Option<&str>) -> Result<String, String> {
+    let mut input = String::new();
+    match path {
+        None | Some("-") => std::io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|error| format!("could not read stdin: {}", error))?,
+        Some(path) => std::fs::File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut input))
+            .map_err(|error| format!("could not read {}: {}", path, error))?,
+    };
+    Ok(input)
+}
+
+fn format_move(move_: &Move) -> String {
+    let robot = ROBOT_ICONS
+        .get(move_.robot)
+        .map(|icon| icon.to_string())
+        .unwrap_or_else(|| format!("robot {}", move_.robot));
+    let arrow = match move_.direction {
+        Direction::Up => "⬆️",
+        Direction::Left => "⬅️",
+        Direction::Down => "⬇️",
+        Direction::Right => "➡️",
+    };
+    format!("{} {}", robot, arrow)
+}
+
+fn show_progress(progress: &Progress) {
+    eprint!(
+        "\r\x1b[KSearching solutions of {} moves: {} positions explored, {} queued ({:.1}s)",
+        progress.depth,
+        progress.explored_positions,
+        progress.queued_sequences,
+        progress.elapsed.as_secs_f64()
+    );
+}
+
+fn read_puzzle(arguments: &Arguments) -> Result<Puzzle, String> {
+    let input = read_input(arguments.path.as_deref())?;
+    match arguments.format.as_str() {
+        "text" => puzzle_file::parse(&input),
+        "ascii" => puzzle_file::parse_ascii(&input),
+        "json" => puzzle_file::parse_json(&input),
+        format => Err(format!("unknown format `{}`", format)),
+    }
+}
+
+fn generate(arguments: &Arguments, min_moves: usize, max_moves: usize) -> ExitCode {
+    let seed = arguments.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let (board, num_robots) = match &arguments.path {
+        Some(_) => match read_puzzle(arguments)
+            .and_then(|puzzle| puzzle.into_parts().map_err(|error| error.to_string()))
+        {
+            Ok((board, robot_positions, _)) => (board, robot_positions.num_robots()),
+            Err(error) => {
+                eprintln!("error: {}", error);
+                return ExitCode::from(2);
+            }
+        },
+        None => (BoardLayout::random(&mut rng).board(), 4),
+    };
+    let options = GenerateOptions {
+        num_robots,
+        ..GenerateOptions::new(min_moves, max_moves)
+    };
+    match generator::generate(&board, &mut rng, &options) {
+        Ok(Some(generated)) => {
+            let mut puzzle = Puzzle::new(
+                board.get_wall_configuration().clone(),
+                &generated.robot_positions,
+                generated.target,
+            );
+            puzzle.mirrors = board.get_mirrors().to_vec();
+            puzzle.metadata = Some(PuzzleMetadata {
+                name: Some(format!("Random puzzle (seed {})", seed)),
+                description: Some(format!("Solved in {} moves", generated.solution.len())),
+                ..PuzzleMetadata::default()
+            });
+            println!("{}", puzzle.to_json());
+            ExitCode::SUCCESS
+        }
+        Ok(None) => {
+            eprintln!("No puzzle found (seed {})", seed);
+            ExitCode::FAILURE
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn format_robot_positions<P: RobotPositions>(robot_positions: &P) -> String {
+    (0..robot_positions.num_robots())
+        .map(|robot| {
+            let position = robot_positions.get_robot_position(robot);
+            let robot = ROBOT_ICONS
+                .get(robot)
+                .map(|icon| icon.to_string())
+                .unwrap_or_else(|| format!("robot {}", robot));
+            format!("{} {} {}", robot, position.row, position.col)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn analyze(puzzle: Puzzle) -> ExitCode {
+    let analysis = puzzle
+        .into_parts()
+        .and_then(|(board, robot_positions, target)| {
+            analysis::analyze(&board, robot_positions.num_robots(), &target)
+        });
+    let analysis = match analysis {
+        Ok(analysis) => analysis,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return ExitCode::from(2);
+        }
+    };
+    println!("Placements by number of moves of their shortest solutions:");
+    for (moves, count) in analysis.histogram.iter().enumerate() {
+        println!("{:>3} moves: {}", moves, count);
+    }
+    println!("   unsolvable: {}", analysis.unsolvable);
+    println!("Hardest placements ({} moves):", analysis.gods_number());
+    // there can be a lot of them
+    for robot_positions in analysis.hardest.iter().take(10) {
+        println!("  {}", format_robot_positions(robot_positions));
+    }
+    if analysis.hardest.len() > 10 {
+        println!("  ... and {} more", analysis.hardest.len() - 10);
+    }
+    ExitCode::SUCCESS
+}
+
+fn print_distances(puzzle: Puzzle, robot: usize, max_moves: Option<usize>) -> ExitCode {
+    let (board, robot_positions, _) = match puzzle.into_parts() {
+        Ok(parts) => parts,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return ExitCode::from(2);
+        }
+    };
+    if let Err(error) = solver::validate_robot(&board, &robot_positions, robot) {
+        eprintln!("error: {}", error);
+        return ExitCode::from(2);
+    }
+    let distances = match max_moves {
+        Some(max_moves) => {
+            board.distance_map_with_moving_robots(robot, &robot_positions, max_moves)
+        }
+        None => board.distance_map(robot, &robot_positions),
+    };
+    for row in 0..board.get_height() {
+        let line: String = (0..board.get_width())
+            .map(|col| {
+                if board.is_blocked(&Position::new(row, col)) {
+                    return "  #".to_string();
+                }
+                match distances[row * board.get_width() + col] {
+                    Some(moves) => format!("{:>3}", moves),
+                    None => "  .".to_string(),
+                }
+            })
+            .collect();
+        println!("{}", line);
+    }
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    if std::env::args().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let arguments = match parse_arguments(std::env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    if let Some((min_moves, max_moves)) = arguments.generate {
+        return generate(&arguments, min_moves, max_moves);
+    }
+
+    let puzzle = match read_puzzle(&arguments) {
+        Ok(puzzle) => puzzle,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return ExitCode::from(2);
+        }
+    };
+    if arguments.analyze {
+        return analyze(puzzle);
+    }
+    if let Some(robot) = arguments.distances {
+        let max_moves = arguments
+            .all_robots_move
+            .then(|| arguments.max_moves.unwrap_or(5));
+        return print_distances(puzzle, robot, max_moves);
+    }
+
+    let progress_line = std::io::stderr().is_terminal();
+    let options = SolveOptions {
+        max_moves: arguments.max_moves,
+        max_explored_positions: arguments.max_positions,
+        progress_observer: progress_line.then_some(&show_progress as _),
+        ..SolveOptions::default()
+    };
+    let outcome = puzzle
+        .into_parts()
+        .and_then(|(board, robot_positions, target)| {
+            solver::solve_with_options(
+                &board,
+                robot_positions,
+                MoveSequenceLinkedList::empty(),
+                target,
+                &options,
+            )
+        });
+    if progress_line {
+        eprint!("\r\x1b[K");
+    }
+    match outcome {
+        Ok(SolveOutcome::Solved(solutions)) => {
+            let moves = solutions[0].moves();
+            println!("Found solution in {} moves:", moves.len());
+            for (index, (move_, _)) in moves.iter().enumerate() {
+                println!("{:>3}. {}", index + 1, format_move(move_));
+            }
+            ExitCode::SUCCESS
+        }
+        Ok(SolveOutcome::NoSolution) => {
+            println!("No solution");
+            ExitCode::FAILURE
+        }
+        Ok(SolveOutcome::LimitReached(limit)) => {
+            match limit {
+                SearchLimit::MaxMoves => println!(
+                    "No solution in at most {} moves",
+                    options.max_moves.unwrap_or_default()
+                ),
+                SearchLimit::MaxExploredPositions => println!(
+                    "Gave up after exploring {} positions",
+                    options.max_explored_positions.unwrap_or_default()
+                ),
+                SearchLimit::Cancelled => println!("Gave up"),
+            }
+            ExitCode::from(3)
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use ricochet_robots_solver::solver::{
+    ascii_board::AsciiBoard, puzzle::Puzzle, robot_positions::RobotPositionsVec,
+    wall_configuration::WallConfigurationVecVec, Mirror, MirrorOrientation, Position, Target,
+};
+
+fn parse_usize(token: Option<&str>, line_number: usize) -> Result<usize, String> {
+    let token = token.ok_or(format!("line {}: missing number", line_number))?;
+    token
+        .parse()
+        .map_err(|_| format!("line {}: invalid number `{}`", line_number, token))
+}
+
+fn parse_position<'a>(
+    tokens: &mut impl Iterator<Item = &'a str>,
+    line_number: usize,
+) -> Result<Position, String> {
+    let row = parse_usize(tokens.next(), line_number)?;
+    let col = parse_usize(tokens.next(), line_number)?;
+    Ok(Position::new(row, col))
+}
+
+// Puzzle description in a line-based format:
+//
+//   # comments start with a hash
+//   size <height> <width>
+//   wall right <row> <col>
+//   wall bottom <row> <col>
+//   blocked <row> <col>          (square that robots can't enter)
+//   mirror <robot> </|\> <row> <col> (robots of other colors bounce off it)
+//   robot <row> <col>            (robots are numbered in order of appearance)
+//   target <robot|*> <row> <col> (`*` means any robot)
+pub fn parse(input: &str) -> Result<Puzzle, String> {
+    let mut size = None;
+    let mut right_walls = vec![];
+    let mut bottom_walls = vec![];
+    let mut blocked_squares = vec![];
+    let mut mirrors = vec![];
+    let mut robots = vec![];
+    let mut target = None;
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.split('#').next().unwrap_or_default();
+        let mut tokens = line.split_whitespace();
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+        match keyword {
+            "size" => {
+                let height = parse_usize(tokens.next(), line_number)?;
+                let width = parse_usize(tokens.next(), line_number)?;
+                size = Some((height, width));
+            }
+            "wall" => {
+                let side = tokens.next();
+                let position = parse_position(&mut tokens, line_number)?;
+                match side {
+                    Some("right") => right_walls.push(position),
+                    Some("bottom") => bottom_walls.push(position),
+                    _ => {
+                        return Err(format!(
+                            "line {}: walls are either `right` or `bottom`",
+                            line_number
+                        ))
+                    }
+                }
+            }
+            "blocked" => blocked_squares.push(parse_position(&mut tokens, line_number)?),
+            "mirror" => {
+                let color = parse_usize(tokens.next(), line_number)?;
+                let orientation = match tokens.next() {
+                    Some("/") => MirrorOrientation::Slash,
+                    Some("\\") => MirrorOrientation::Backslash,
+                    _ => {
+                        return Err(format!(
+                            "line {}: mirrors are either `/` or `\\`",
+                            line_number
+                        ))
+                    }
+                };
+                mirrors.push(Mirror {
+                    position: parse_position(&mut tokens, line_number)?,
+                    orientation,
+                    color,
+                });
+            }
+            "robot" => robots.push(parse_position(&mut tokens, line_number)?),
+            "target" => {
+                let robot = match tokens.next() {
+                    Some("*") => None,
+                    token => Some(parse_usize(token, line_number)?),
+                };
+                let position = parse_position(&mut tokens, line_number)?;
+                target = Some(match robot {
+                    Some(robot) => Target::Robot(robot, position),
+                    None => Target::AnyRobot(position),
+                });
+            }
+            _ => {
+                return Err(format!(
+                    "line {}: unknown keyword `{}`",
+                    line_number, keyword
+                ))
+            }
+        }
+        if let Some(token) = tokens.next() {
+            return Err(format!(
+                "line {}: unexpected token `{}`",
+                line_number, token
+            ));
+        }
+    }
+
+    let (height, width) = size.ok_or("missing `size` line")?;
+    let target = target.ok_or("missing `target` line")?;
+    let mut wall_configuration = WallConfigurationVecVec {
+        height,
+        width,
+        right_walls: vec![vec![]; height],
+        bottom_walls: vec![vec![]; width],
+        blocked_squares,
+    };
+    for position in right_walls {
+        wall_configuration
+            .right_walls
+            .get_mut(position.row)
+            .ok_or(format!("wall outside of the board: {:?}", position))?
+            .push(position.col);
+    }
+    for position in bottom_walls {
+        wall_configuration
+            .bottom_walls
+            .get_mut(position.col)
+            .ok_or(format!("wall outside of the board: {:?}", position))?
+            .push(position.row);
+    }
+    Ok(Puzzle {
+        mirrors,
+        ..Puzzle::new(wall_configuration, &RobotPositionsVec::new(robots), target)
+    })
+}
+
+// Puzzle drawn with the ASCII art format of `AsciiBoard`
+pub fn parse_ascii(input: &str) -> Result<Puzzle, String> {
+    let board: AsciiBoard = input.parse().map_err(|error| format!("{}", error))?;
+    Ok(Puzzle::new(
+        board.wall_configuration,
+        &board.robot_positions,
+        board.target.ok_or("the board has no target")?,
+    ))
+}
+
+// Puzzle in the JSON format of `Puzzle`, as exported by the web app
+pub fn parse_json(input: &str) -> Result<Puzzle, String> {
+    Puzzle::from_json(input).map_err(|error| format!("{}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_robots_solver::solver::puzzle::PuzzleTarget;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let puzzle = parse(
+            "
+            # board from the solver tests
+            size 6 5
+            wall right 1 2
+            wall bottom 1 2
+            blocked 3 3
+            mirror 1 \\ 2 2
+            robot 0 0
+            robot 1 0 # second robot
+            target * 4 3
+            ",
+        )
+        .unwrap();
+        assert_eq!(puzzle.height, 6);
+        assert_eq!(puzzle.width, 5);
+        assert_eq!(
+            puzzle.right_walls,
+            vec![vec![], vec![2], vec![], vec![], vec![], vec![]]
+        );
+        assert_eq!(
+            puzzle.bottom_walls,
+            vec![vec![], vec![], vec![1], vec![], vec![]]
+        );
+        assert_eq!(puzzle.blocked_squares, vec![Position::new(3, 3)]);
+        assert_eq!(
+            puzzle.mirrors,
+            vec![Mirror {
+                position: Position::new(2, 2),
+                orientation: MirrorOrientation::Backslash,
+                color: 1
+            }]
+        );
+        assert_eq!(
+            puzzle.robots,
+            vec![Position::new(0, 0), Position::new(1, 0)]
+        );
+        assert_eq!(
+            puzzle.target,
+            PuzzleTarget {
+                robot: None,
+                position: Position::new(4, 3)
+            }
+        );
+
+        let cases = vec![
+            "robot 0 0\ntarget 0 1 1",
+            "size 3 3\nrobot 0 0",
+            "size 3 3\nwall left 0 0\ntarget * 1 1",
+            "size 3 3\nmirror 0 | 1 1\ntarget * 1 1",
+            "size 3 3\nrobot 0 zero\ntarget * 1 1",
+            "size 3 3\nrobot 0 0 0\ntarget * 1 1",
+            "size 3 3\nwall right 3 0\ntarget * 1 1",
+        ];
+        for input in cases {
+            assert!(parse(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_ascii() {
+        let puzzle = parse_ascii(
+            "
+             ___
+            |A .|
+            |  _|
+            |b B|
+            |___|
+            ",
+        )
+        .unwrap();
+        assert_eq!(puzzle.robots.len(), 2);
+        assert_eq!(
+            puzzle.target,
+            PuzzleTarget {
+                robot: Some(1),
+                position: Position::new(1, 0)
+            }
+        );
+
+        assert!(parse_ascii(" ___\n|A .|\n|___|").is_err());
+    }
+}
@@ -1,4 +1,4 @@
-mod solver;
+pub mod solver;
 mod wasm;
 
 pub use crate::wasm::*;
@@ -0,0 +1,253 @@
+use super::{
+    board::Board,
+    error::SolveError,
+    robot_positions::{RobotPositions, RobotPositionsVec},
+    solver::validate,
+    wall_configuration::WallConfiguration,
+    Position, Target,
+};
+
+// Beyond this, the distances don't fit in memory (one byte per placement)
+pub const MAX_STATES: u64 = 1 << 28;
+
+// Marks the placements whose distance to the target isn't known (yet)
+const UNKNOWN: u8 = u8::MAX;
+
+// Length of the shortest solutions of every placement of the robots on a
+// board, for a given target
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateSpaceAnalysis {
+    // number of placements by number of moves of their shortest solutions
+    pub histogram: Vec<usize>,
+    // number of placements from which the target can't be reached
+    pub unsolvable: usize,
+    // placements with the longest shortest solutions
+    pub hardest: Vec<RobotPositionsVec>,
+}
+
+impl StateSpaceAnalysis {
+    // Number of moves that is always enough to reach the target, from any
+    // placement from which it can be reached
+    pub fn gods_number(&self) -> usize {
+        self.histogram.len().saturating_sub(1)
+    }
+}
+
+// Placements of the robots, numbered by reading the squares of the robots as
+// the digits of a number in base `width * height`
+struct States<'a, W: WallConfiguration> {
+    board: &'a Board<W>,
+    num_squares: usize,
+    num_robots: usize,
+}
+
+impl<W: WallConfiguration> States<'_, W> {
+    fn count(&self) -> usize {
+        self.num_squares.pow(self.num_robots as u32)
+    }
+
+    fn index(&self, robot_positions: &RobotPositionsVec) -> usize {
+        (0..self.num_robots).rev().fold(0, |index, robot| {
+            let position = robot_positions.get_robot_position(robot);
+            index * self.num_squares + position.row * self.board.get_width() + position.col
+        })
+    }
+
+    // `None` if robots are on blocked squares or on top of each other
+    fn robot_positions(&self, mut index: usize) -> Option<RobotPositionsVec> {
+        let mut positions: Vec<Position> = Vec::with_capacity(self.num_robots);
+        for _ in 0..self.num_robots {
+            let square = index % self.num_squares;
+            index /= self.num_squares;
+            let position = Position::new(
+                square / self.board.get_width(),
+                square % self.board.get_width(),
+            );
+            if self.board.is_blocked(&position) || positions.contains(&position) {
+                return None;
+            }
+            positions.push(position);
+        }
+        Some(RobotPositionsVec::new(positions))
+    }
+}
+
+// Exhaustive search over every placement of `num_robots` robots, going
+// backwards from the placements where the target is reached: the placements
+// from which a robot slides into a placement `n` moves away from the target are
+// `n + 1` moves away, unless they are closer. On boards with mirrors, which
+// have no predecessors (see `Board::get_predecessors_for_robot`), each layer is
+// found by trying the moves of every placement left instead, which is much
+// slower.
+//
+// There are `(width * height) ^ num_robots` placements, so this is only
+// practical with 2 or 3 robots on a full board.
+pub fn analyze<W: WallConfiguration>(
+    board: &Board<W>,
+    num_robots: usize,
+    target: &Target,
+) -> Result<StateSpaceAnalysis, SolveError> {
+    // only the number of robots matters, any placement will do
+    let squares = (0..board.get_height())
+        .flat_map(|row| (0..board.get_width()).map(move |col| Position::new(row, col)))
+        .filter(|position| !board.is_blocked(position));
+    validate(
+        board,
+        &RobotPositionsVec::new(squares.take(num_robots).collect()),
+        target,
+    )?;
+    let states = States {
+        board,
+        num_squares: board.get_width() * board.get_height(),
+        num_robots,
+    };
+    let num_states = (states.num_squares as u64)
+        .checked_pow(num_robots as u32)
+        .filter(|num_states| *num_states <= MAX_STATES)
+        .ok_or(SolveError::StateSpaceTooLarge { num_robots })?;
+
+    let mut distances = vec![UNKNOWN; num_states as usize];
+    let mut layer: Vec<usize> = (0..states.count())
+        .filter(|index| {
+            states
+                .robot_positions(*index)
+                .is_some_and(|robot_positions| target.is_reached(&robot_positions))
+        })
+        .collect();
+    let mut histogram = vec![];
+    while !layer.is_empty() {
+        let moves = histogram.len() as u8;
+        for index in &layer {
+            distances[*index] = moves;
+        }
+        histogram.push(layer.len());
+        let mut next_layer = vec![];
+        if board.get_mirrors().is_empty() {
+            for index in &layer {
+                let robot_positions = states.robot_positions(*index).unwrap();
+                for robot in 0..num_robots {
+                    for (_, previous) in board.get_predecessors_for_robot(robot, &robot_positions) {
+                        let previous = states.index(&robot_positions.update(robot, previous));
+                        if distances[previous] == UNKNOWN {
+                            // not the final distance, but keeps it out of the
+                            // layer if another robot leads to it too
+                            distances[previous] = moves + 1;
+                            next_layer.push(previous);
+                        }
+                    }
+                }
+            }
+        } else {
+            for index in 0..states.count() {
+                if distances[index] != UNKNOWN {
+                    continue;
+                }
+                let Some(robot_positions) = states.robot_positions(index) else {
+                    continue;
+                };
+                if (0..num_robots).any(|robot| {
+                    board
+                        .get_valid_moves_for_robot(robot, &robot_positions)
+                        .into_iter()
+                        .any(|(_, position)| {
+                            distances[states.index(&robot_positions.update(robot, position))]
+                                == moves
+                        })
+                }) {
+                    next_layer.push(index);
+                }
+            }
+        }
+        if next_layer.is_empty() {
+            break;
+        }
+        layer = next_layer;
+    }
+
+    let solvable: usize = histogram.iter().sum();
+    let num_placements = (0..states.count())
+        .filter(|index| states.robot_positions(*index).is_some())
+        .count();
+    Ok(StateSpaceAnalysis {
+        histogram,
+        unsolvable: num_placements - solvable,
+        hardest: layer
+            .into_iter()
+            .map(|index| states.robot_positions(index).unwrap())
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{
+        ascii_board::AsciiBoard,
+        boards::BoardLayout,
+        move_sequence::{MoveSequence, MoveSequenceLinkedList},
+        solver::{BestFirstSolver, Solver},
+        Mirror, MirrorOrientation,
+    };
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_analyze() {
+        let AsciiBoard {
+            wall_configuration, ..
+        } = "
+             _______
+            |. . . .|
+            |       |
+            |. . .|.|
+            |  _    |
+            |. # . .|
+            |       |
+            |. . . .|
+            |_______|
+        "
+        .parse()
+        .unwrap();
+        let target = Target::Robot(0, Position::new(2, 2));
+        let boards = [
+            Board::new(wall_configuration.clone()),
+            Board::new(wall_configuration).with_mirrors(vec![Mirror {
+                position: Position::new(1, 0),
+                orientation: MirrorOrientation::Slash,
+                color: 1,
+            }]),
+        ];
+        for board in boards {
+            let analysis = analyze(&board, 3, &target).unwrap();
+            // 15 squares for the first robot, 14 for the second and 13 for
+            // the third
+            assert_eq!(
+                analysis.histogram.iter().sum::<usize>() + analysis.unsolvable,
+                15 * 14 * 13
+            );
+            assert_eq!(analysis.histogram[0], 14 * 13);
+            assert_eq!(
+                analysis.hardest.len(),
+                analysis.histogram[analysis.gods_number()]
+            );
+            // same lengths as the solver
+            for robot_positions in &analysis.hardest {
+                let solution = BestFirstSolver::default()
+                    .solve(
+                        &board,
+                        robot_positions.clone(),
+                        MoveSequenceLinkedList::empty(),
+                        target.clone(),
+                    )
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(solution.moves().len(), analysis.gods_number());
+            }
+        }
+
+        assert_eq!(
+            analyze(&BoardLayout::default().board(), 4, &target),
+            Err(SolveError::StateSpaceTooLarge { num_robots: 4 })
+        );
+    }
+}
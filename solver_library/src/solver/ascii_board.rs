@@ -0,0 +1,309 @@
+use std::{fmt, str::FromStr};
+
+use super::{
+    robot_positions::{RobotPositions, RobotPositionsVec},
+    wall_configuration::WallConfigurationVecVec,
+    Position, Target,
+};
+
+// Board, robots and target drawn as ASCII art, to make test fixtures and bug
+// reports readable:
+//
+//    _________
+//   |A . . . .|
+//   |         |
+//   |B . C|. D|
+//   |    _    |
+//   |. . . . a|
+//   |_________|
+//
+// Each square is drawn as a single character (`.` for an empty square, `A` to
+// `Z` for robots 0 to 25, `a` to `z` for a target that has to be reached by
+// robots 0 to 25, `*` for a target that can be reached by any robot, and `#`
+// for a blocked square).
+// Walls on the right of squares are drawn with `|` between squares, and walls
+// on the bottom of squares are drawn with `_` in the line below the squares.
+//
+// Leading and trailing whitespace is ignored on each line, so that boards can
+// be indented in the source code.
+//
+// A target hidden under a robot can't be drawn, so it is omitted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsciiBoard {
+    pub wall_configuration: WallConfigurationVecVec,
+    pub robot_positions: RobotPositionsVec,
+    pub target: Option<Target>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    // 1-based, counting only non-blank lines
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn error(line: usize, message: impl Into<String>) -> ParseError {
+    ParseError {
+        line,
+        message: message.into(),
+    }
+}
+
+impl FromStr for AsciiBoard {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<Vec<char>> = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().collect())
+            .collect();
+
+        let top_border = lines.first().ok_or(error(1, "empty board"))?;
+        if top_border.iter().any(|c| *c != '_') || top_border.len().is_multiple_of(2) {
+            return Err(error(1, "the top border should be an odd number of `_`"));
+        }
+        let width = top_border.len().div_ceil(2);
+        if lines.len() < 3 || lines.len().is_multiple_of(2) {
+            return Err(error(
+                lines.len(),
+                "each row should be drawn with two lines (squares, then bottom walls)",
+            ));
+        }
+        let height = (lines.len() - 1) / 2;
+
+        let mut wall_configuration = WallConfigurationVecVec {
+            height,
+            width,
+            right_walls: vec![vec![]; height],
+            bottom_walls: vec![vec![]; width],
+            blocked_squares: vec![],
+        };
+        let mut robots: Vec<Option<Position>> = vec![];
+        let mut target = None;
+
+        for row in 0..height {
+            for (line_index, is_squares_line) in [(2 * row + 1, true), (2 * row + 2, false)] {
+                let line = &lines[line_index];
+                let line_number = line_index + 1;
+                if line.len() != 2 * width + 1 || line[0] != '|' || line[2 * width] != '|' {
+                    return Err(error(
+                        line_number,
+                        format!("expected {} characters between two `|`", 2 * width - 1),
+                    ));
+                }
+                for col in 0..width {
+                    let square = line[2 * col + 1];
+                    let separator = line[2 * col + 2];
+                    let position = Position::new(row, col);
+                    if !is_squares_line {
+                        match square {
+                            '_' if row + 1 < height => {
+                                wall_configuration.bottom_walls[col].push(row)
+                            }
+                            '_' | ' ' => {}
+                            c => return Err(error(line_number, format!("unexpected `{}`", c))),
+                        }
+                        if separator != ' ' && separator != '_' && col + 1 < width {
+                            return Err(error(line_number, format!("unexpected `{}`", separator)));
+                        }
+                        continue;
+                    }
+                    match square {
+                        '.' => {}
+                        '#' => wall_configuration.blocked_squares.push(position),
+                        'A'..='Z' => {
+                            let robot = (square as u8 - b'A') as usize;
+                            if robots.len() <= robot {
+                                robots.resize(robot + 1, None);
+                            }
+                            if robots[robot].replace(position).is_some() {
+                                return Err(error(
+                                    line_number,
+                                    format!("robot `{}` is drawn twice", square),
+                                ));
+                            }
+                        }
+                        'a'..='z' | '*' => {
+                            let next_target = match square {
+                                '*' => Target::AnyRobot(position),
+                                _ => Target::Robot((square as u8 - b'a') as usize, position),
+                            };
+                            if target.replace(next_target).is_some() {
+                                return Err(error(line_number, "there can only be one target"));
+                            }
+                        }
+                        c => return Err(error(line_number, format!("unexpected `{}`", c))),
+                    }
+                    match separator {
+                        '|' if col + 1 < width => wall_configuration.right_walls[row].push(col),
+                        '|' | ' ' => {}
+                        c => return Err(error(line_number, format!("unexpected `{}`", c))),
+                    }
+                }
+            }
+        }
+
+        let robot_positions = robots
+            .into_iter()
+            .enumerate()
+            .map(|(robot, position)| {
+                position.ok_or(error(
+                    lines.len(),
+                    format!("robot `{}` is missing", (b'A' + robot as u8) as char),
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(AsciiBoard {
+            wall_configuration,
+            robot_positions: RobotPositionsVec::new(robot_positions),
+            target,
+        })
+    }
+}
+
+impl fmt::Display for AsciiBoard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let WallConfigurationVecVec {
+            height,
+            width,
+            right_walls,
+            bottom_walls,
+            blocked_squares,
+        } = &self.wall_configuration;
+        let has_bottom_wall =
+            |row: usize, col: usize| row + 1 == *height || bottom_walls[col].contains(&row);
+
+        writeln!(f, " {} ", "_".repeat(2 * width - 1))?;
+        for (row, row_right_walls) in right_walls.iter().enumerate() {
+            write!(f, "|")?;
+            for col in 0..*width {
+                let position = Position::new(row, col);
+                let robot = (0..self.robot_positions.num_robots())
+                    .find(|robot| self.robot_positions.get_robot_position(*robot) == position);
+                let square = match (robot, &self.target) {
+                    (Some(robot), _) => char::from_u32('A' as u32 + robot as u32).unwrap_or('?'),
+                    (None, Some(Target::Robot(robot, target))) if *target == position => {
+                        char::from_u32('a' as u32 + *robot as u32).unwrap_or('?')
+                    }
+                    (None, Some(Target::AnyRobot(target))) if *target == position => '*',
+                    _ if blocked_squares.contains(&position) => '#',
+                    _ => '.',
+                };
+                let separator = if col + 1 == *width || row_right_walls.contains(&col) {
+                    '|'
+                } else {
+                    ' '
+                };
+                write!(f, "{}{}", square, separator)?;
+            }
+            writeln!(f)?;
+
+            write!(f, "|")?;
+            for col in 0..*width {
+                let floor = if has_bottom_wall(row, col) { '_' } else { ' ' };
+                let separator = if col + 1 == *width {
+                    '|'
+                } else if has_bottom_wall(row, col) && has_bottom_wall(row, col + 1) {
+                    '_'
+                } else {
+                    ' '
+                };
+                write!(f, "{}{}", floor, separator)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_ascii_board() {
+        let drawing = concat!(
+            " _________ \n",
+            "|A . . . .|\n",
+            "|         |\n",
+            "|B . C|. D|\n",
+            "|    _    |\n",
+            "|. # . . .|\n",
+            "|_ _      |\n",
+            "|. . . .|a|\n",
+            "|_________|\n",
+        );
+        let board: AsciiBoard = drawing.parse().unwrap();
+        assert_eq!(
+            board,
+            AsciiBoard {
+                wall_configuration: WallConfigurationVecVec {
+                    height: 4,
+                    width: 5,
+                    right_walls: vec![vec![], vec![2], vec![], vec![3]],
+                    bottom_walls: vec![vec![2], vec![2], vec![1], vec![], vec![]],
+                    blocked_squares: vec![Position::new(2, 1)],
+                },
+                robot_positions: RobotPositionsVec::new(vec![
+                    Position::new(0, 0),
+                    Position::new(1, 0),
+                    Position::new(1, 2),
+                    Position::new(1, 4),
+                ]),
+                target: Some(Target::Robot(0, Position::new(3, 4))),
+            }
+        );
+        assert_eq!(
+            board.to_string(),
+            drawing.replace("|_ _      |", "|___      |")
+        );
+        assert_eq!(board.to_string().parse::<AsciiBoard>().unwrap(), board);
+
+        // indentation is ignored
+        let indented: AsciiBoard = "
+             ___
+            |* .|
+            |___|
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(indented.target, Some(Target::AnyRobot(Position::new(0, 0))));
+        assert_eq!(indented.robot_positions.num_robots(), 0);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_ascii_board_errors() {
+        let cases: Vec<(&str, usize)> = vec![
+            ("", 1),
+            ("_\n|. .|\n|___|", 2),                 // too wide
+            ("___\n|. .|", 2),                      // missing bottom walls
+            ("___\n|. ?|\n|___|", 2),               // unknown square
+            ("___\n|.:.|\n|___|", 2),               // unknown wall
+            ("___\n|A A|\n|___|", 2),               // robot drawn twice
+            ("___\n|a *|\n|___|", 2),               // two targets
+            ("___\n|. B|\n|___|", 3),               // robot A is missing
+            ("___\n|. .|\n|. .|\n|. .|\n|___|", 3), // squares instead of walls
+        ];
+        for (drawing, line) in cases {
+            assert_eq!(
+                drawing.parse::<AsciiBoard>().err().map(|e| e.line),
+                Some(line),
+                "{}",
+                drawing
+            );
+        }
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{
+    board::Board,
+    cost_model::CostModel,
+    error::SolveError,
+    heuristic::TargetDistances,
+    move_sequence::{Move, MoveSequence},
+    robot_positions::RobotPositions,
+    solver::{canonical_positions, validate, BestFirstSolver, SequenceWithCost, Solver},
+    wall_configuration::WallConfiguration,
+    Position, Target,
+};
+
+// Meet-in-the-middle search. Every solution ends with moves of a single robot
+// (the target robot, or any robot for a wildcard target) while the other robots
+// stay where they are. A backward search from the target, going through the
+// squares from which the robot slides into it, tells how many moves it needs to
+// finish on its own from any position. The forward search, breadth-first, then
+// only has to reach a position where the robot can take over: it stops once no
+// position left to explore could lead to a shorter solution, according to
+// `TargetDistances`.
+//
+// Robots don't move in straight lines on boards with mirrors, which are solved
+// with `BestFirstSolver` instead.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BidirectionalSolver;
+
+// Marks the squares from which the robot can't reach the target on its own
+const UNREACHABLE: u8 = u8::MAX;
+
+// Backward search, remembering for every placement of the other robots the
+// number of moves the robot needs to reach the target from each square (as far
+// as it was searched)
+struct FinishingMoves<'a, W: WallConfiguration, P: RobotPositions> {
+    board: &'a Board<W>,
+    target: Position,
+    cache: HashMap<(usize, P), (u8, Vec<u8>)>,
+}
+
+impl<'a, W, P> FinishingMoves<'a, W, P>
+where
+    W: WallConfiguration,
+    P: RobotPositions,
+{
+    fn new(board: &'a Board<W>, target: Position) -> Self {
+        FinishingMoves {
+            board,
+            target,
+            cache: HashMap::new(),
+        }
+    }
+
+    // Backward search from the target, up to `max_moves` moves away from it
+    fn search(&self, robot: usize, at_target: &P, max_moves: u8) -> Vec<u8> {
+        let width = self.board.get_width();
+        let mut distances = vec![UNREACHABLE; self.board.get_height() * width];
+        // another robot is on the target
+        if (0..at_target.num_robots())
+            .any(|other| other != robot && at_target.get_robot_position(other) == self.target)
+        {
+            return distances;
+        }
+        distances[self.target.row * width + self.target.col] = 0;
+        let mut queue = VecDeque::from([self.target]);
+        while let Some(position) = queue.pop_front() {
+            let moves = distances[position.row * width + position.col];
+            if moves == max_moves {
+                continue;
+            }
+            for (_, previous) in self
+                .board
+                .get_predecessors_for_robot(robot, &at_target.update(robot, position))
+            {
+                let distance = &mut distances[previous.row * width + previous.col];
+                if *distance == UNREACHABLE {
+                    *distance = moves + 1;
+                    queue.push_back(previous);
+                }
+            }
+        }
+        distances
+    }
+
+    // Number of moves the robot needs to reach the target on its own, if it
+    // needs at most `max_moves` of them
+    fn get(&mut self, robot: usize, robot_positions: &P, max_moves: usize) -> Option<usize> {
+        // further squares don't fit in the distances
+        let max_moves = max_moves.min(UNREACHABLE as usize - 1) as u8;
+        // only the other robots matter, so the robot is moved to the target
+        let key = (robot, robot_positions.update(robot, self.target));
+        let searched = match self.cache.get(&key) {
+            Some((searched_moves, distances)) if *searched_moves >= max_moves => distances,
+            _ => {
+                let distances = self.search(robot, &key.1, max_moves);
+                &self
+                    .cache
+                    .entry(key)
+                    .insert_entry((max_moves, distances))
+                    .into_mut()
+                    .1
+            }
+        };
+        let position = robot_positions.get_robot_position(robot);
+        let moves = searched[position.row * self.board.get_width() + position.col];
+        (moves <= max_moves).then_some(moves as usize)
+    }
+
+    // Appends the moves of the robot to the target to `sequence`
+    fn finish<M: MoveSequence<P>>(
+        &mut self,
+        robot: usize,
+        mut robot_positions: P,
+        mut sequence: M,
+    ) -> M {
+        while let Some(moves) = self
+            .get(robot, &robot_positions, usize::MAX)
+            .filter(|moves| *moves > 0)
+        {
+            let (direction, next_robot_positions) = self
+                .board
+                .get_valid_moves_for_robot(robot, &robot_positions)
+                .into_iter()
+                .map(|(direction, position)| (direction, robot_positions.update(robot, position)))
+                .find(|(_, next_robot_positions)| {
+                    self.get(robot, next_robot_positions, moves - 1) == Some(moves - 1)
+                })
+                .expect("the distances lead to the target");
+            sequence = sequence.append(Move { robot, direction }, next_robot_positions.clone());
+            robot_positions = next_robot_positions;
+        }
+        sequence
+    }
+}
+
+impl Solver for BidirectionalSolver {
+    fn solve<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
+        &self,
+        board: &Board<W>,
+        robot_positions: P,
+        empty_move_sequence: M,
+        target: Target,
+    ) -> Result<Option<SequenceWithCost<P, M>>, SolveError> {
+        validate(board, &robot_positions, &target)?;
+        if !board.get_mirrors().is_empty() {
+            return BestFirstSolver::default().solve(
+                board,
+                robot_positions,
+                empty_move_sequence,
+                target,
+            );
+        }
+
+        let distances = TargetDistances::new(board, target.position());
+        let mut finishing_moves = FinishingMoves::new(board, *target.position());
+        let finishing_robots: Vec<usize> = match target {
+            Target::Robot(robot, _) => vec![robot],
+            Target::AnyRobot(_) => (0..robot_positions.num_robots()).collect(),
+        };
+
+        // shortest solution so far: its length, the position where the robot
+        // takes over and how it got there
+        let mut best: Option<(usize, usize, M, P)> = None;
+        let mut take_over = |moves: usize, sequence: &M, robot_positions: &P| {
+            for robot in &finishing_robots {
+                // only shorter solutions are interesting
+                let max_finish = match &best {
+                    Some(best) if best.0 <= moves => break,
+                    Some(best) => best.0 - moves - 1,
+                    None => usize::MAX,
+                };
+                let Some(finish) = finishing_moves.get(*robot, robot_positions, max_finish) else {
+                    continue;
+                };
+                if best.as_ref().is_none_or(|best| moves + finish < best.0) {
+                    best = Some((
+                        moves + finish,
+                        *robot,
+                        sequence.clone(),
+                        robot_positions.clone(),
+                    ));
+                }
+            }
+            best.as_ref().map(|best| best.0)
+        };
+        // positions that can't lead to a solution shorter than `shortest`
+        let is_useless = |moves: usize, robot_positions: &P, shortest: Option<usize>| {
+            distances
+                .estimate(&target, robot_positions)
+                .is_none_or(|estimate| {
+                    shortest.is_some_and(|shortest| moves + estimate >= shortest)
+                })
+        };
+
+        let mut explored = HashSet::new();
+        explored.insert(canonical_positions(board, &target, &robot_positions));
+        let mut shortest = take_over(0, &empty_move_sequence, &robot_positions);
+        let mut layer = vec![(empty_move_sequence, robot_positions)];
+        for moves in 0.. {
+            let mut next_layer = vec![];
+            for (sequence, robot_positions) in &layer {
+                // a shorter solution was found after it was queued
+                if is_useless(moves, robot_positions, shortest) {
+                    continue;
+                }
+                for robot in 0..robot_positions.num_robots() {
+                    for (direction, position) in
+                        board.get_valid_moves_for_robot(robot, robot_positions)
+                    {
+                        let next_robot_positions = robot_positions.update(robot, position);
+                        if is_useless(moves + 1, &next_robot_positions, shortest)
+                            || !explored.insert(canonical_positions(
+                                board,
+                                &target,
+                                &next_robot_positions,
+                            ))
+                        {
+                            continue;
+                        }
+                        let next_sequence = sequence
+                            .append(Move { robot, direction }, next_robot_positions.clone());
+                        shortest = take_over(moves + 1, &next_sequence, &next_robot_positions);
+                        next_layer.push((next_sequence, next_robot_positions));
+                    }
+                }
+            }
+            if next_layer.is_empty() {
+                break;
+            }
+            layer = next_layer;
+        }
+
+        Ok(best.map(|(_, robot, sequence, robot_positions)| {
+            SequenceWithCost::new(
+                finishing_moves.finish(robot, robot_positions, sequence),
+                &target,
+                &CostModel::default(),
+            )
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{
+        ascii_board::AsciiBoard, boards::BoardLayout, move_sequence::MoveSequenceLinkedList,
+        robot_positions::RobotPositionsVec,
+    };
+    use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_bidirectional_solver() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _________
+            |A . . . .|
+            |         |
+            |B . C|. D|
+            |    _    |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |_________|
+        "
+        .parse()
+        .unwrap();
+        let board = Board::new(wall_configuration);
+        let empty_move_sequence = MoveSequenceLinkedList::<RobotPositionsVec>::empty();
+        let cases = [
+            (Target::Robot(0, Position::new(0, 0)), 0),
+            (Target::Robot(0, Position::new(4, 3)), 5),
+            (Target::Robot(3, Position::new(0, 0)), 4),
+            (Target::Robot(0, Position::new(3, 1)), 8),
+            (Target::Robot(1, Position::new(2, 3)), 6),
+            (Target::AnyRobot(Position::new(3, 1)), 7),
+            (Target::AnyRobot(Position::new(3, 3)), 7),
+        ];
+        for (target, moves) in cases {
+            let solution = BidirectionalSolver
+                .solve(
+                    &board,
+                    robot_positions.clone(),
+                    empty_move_sequence.clone(),
+                    target.clone(),
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(solution.moves().len(), moves, "{:?}", target);
+            // the moves have to be valid, and end on the target
+            let mut current = robot_positions.clone();
+            for (move_, next) in solution.moves() {
+                assert!(board
+                    .get_valid_moves_for_robot(move_.robot, &current)
+                    .contains(&(move_.direction, next.get_robot_position(move_.robot))));
+                current = next;
+            }
+            assert!(target.is_reached(&current));
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_bidirectional_solver_on_random_puzzles() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        for _ in 0..5 {
+            let board = BoardLayout::random(&mut rng).board();
+            let free_squares: Vec<Position> = (0..board.get_height())
+                .flat_map(|row| (0..board.get_width()).map(move |col| Position::new(row, col)))
+                .filter(|position| !board.is_blocked(position))
+                .collect();
+            let robot_positions = RobotPositionsVec::new(
+                free_squares.choose_multiple(&mut rng, 4).cloned().collect(),
+            );
+            let target = board.get_targets().choose(&mut rng).unwrap().target();
+            let expected = BestFirstSolver::default()
+                .solve(
+                    &board,
+                    robot_positions.clone(),
+                    MoveSequenceLinkedList::empty(),
+                    target.clone(),
+                )
+                .unwrap()
+                .map(|solution| solution.moves().len());
+            let moves = BidirectionalSolver
+                .solve(
+                    &board,
+                    robot_positions.clone(),
+                    MoveSequenceLinkedList::empty(),
+                    target.clone(),
+                )
+                .unwrap()
+                .map(|solution| solution.moves().len());
+            assert_eq!(moves, expected, "{:?} {:?}", robot_positions, target);
+        }
+    }
+}
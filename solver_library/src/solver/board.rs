@@ -1,12 +1,24 @@
+use std::collections::{HashSet, VecDeque};
+
 use super::robot_positions::RobotPositions;
 use super::wall_configuration::WallConfiguration;
-use super::{Direction, Position};
+use super::{Direction, Mirror, Position, TargetSquare};
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Right,
+    Direction::Left,
+];
 
 // Immutable container for the board setup
 pub struct Board<W: WallConfiguration> {
     wall_configuration: W,
-    // TODO: add field for mirrors
-    // TODO: add field for targets
+    // square where a robot starting on each square stops in each direction
+    // (in the order of `DIRECTIONS`), ignoring robots and mirrors
+    slides: Vec<[Position; 4]>,
+    mirrors: Vec<Mirror>,
+    targets: Vec<TargetSquare>,
 }
 
 impl<W> Board<W>
@@ -14,25 +26,127 @@ where
     W: WallConfiguration,
 {
     pub fn new(wall_configuration: W) -> Self {
-        Board { wall_configuration }
+        Board {
+            slides: Board::compute_slides(&wall_configuration),
+            wall_configuration,
+            mirrors: vec![],
+            targets: vec![],
+        }
+    }
+
+    pub fn get_wall_configuration(&self) -> &W {
+        &self.wall_configuration
+    }
+
+    // Same board with another representation of the walls
+    pub fn with_wall_configuration<V: WallConfiguration>(self, wall_configuration: V) -> Board<V> {
+        Board {
+            slides: Board::compute_slides(&wall_configuration),
+            wall_configuration,
+            mirrors: self.mirrors,
+            targets: self.targets,
+        }
+    }
+
+    pub fn with_mirrors(self, mirrors: Vec<Mirror>) -> Self {
+        Board { mirrors, ..self }
+    }
+
+    pub fn get_mirrors(&self) -> &[Mirror] {
+        &self.mirrors
+    }
+
+    // Target squares printed on the board, one of them is drawn at each round
+    // of a game
+    pub fn with_targets(self, targets: Vec<TargetSquare>) -> Self {
+        Board { targets, ..self }
+    }
+
+    pub fn get_targets(&self) -> &[TargetSquare] {
+        &self.targets
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.wall_configuration.is_valid()
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.wall_configuration.get_height()
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.wall_configuration.get_width()
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        position.row < self.get_height() && position.col < self.get_width()
+    }
+
+    pub fn is_blocked(&self, position: &Position) -> bool {
+        self.wall_configuration.is_blocked(position)
     }
+
+    // The walls of invalid boards can't be trusted (they can be outside of the
+    // board), so robots don't move at all on them. Searches report them as
+    // errors anyway (see `solver::validate`).
+    fn compute_slides(wall_configuration: &W) -> Vec<[Position; 4]> {
+        let height = wall_configuration.get_height();
+        let width = wall_configuration.get_width();
+        let positions =
+            (0..height).flat_map(|row| (0..width).map(move |col| Position::new(row, col)));
+        if !wall_configuration.is_valid() {
+            return positions.map(|position| [position; 4]).collect();
+        }
+        positions
+            .map(|position| {
+                DIRECTIONS.map(|direction| match direction {
+                    Direction::Up => Position::new(
+                        wall_configuration.next_wall_up(&position).unwrap_or(0),
+                        position.col,
+                    ),
+                    Direction::Down => Position::new(
+                        wall_configuration
+                            .next_wall_down(&position)
+                            .unwrap_or(height - 1),
+                        position.col,
+                    ),
+                    Direction::Right => Position::new(
+                        position.row,
+                        wall_configuration
+                            .next_wall_right(&position)
+                            .unwrap_or(width - 1),
+                    ),
+                    Direction::Left => Position::new(
+                        position.row,
+                        wall_configuration.next_wall_left(&position).unwrap_or(0),
+                    ),
+                })
+            })
+            .collect()
+    }
+
+    // Last square before a wall, ignoring robots and mirrors
+    pub fn get_farthest_square(&self, position: &Position, direction: Direction) -> Position {
+        let index = match direction {
+            Direction::Up => 0,
+            Direction::Down => 1,
+            Direction::Right => 2,
+            Direction::Left => 3,
+        };
+        self.slides[position.row * self.get_width() + position.col][index]
+    }
+
     fn get_valid_up_move<P: RobotPositions>(
         &self,
         robot: usize,
         robot_positions: &P,
     ) -> Option<Position> {
         let position = robot_positions.get_robot_position(robot);
-        let up_wall = self.wall_configuration.next_wall_up(position);
-        let up_robot = robot_positions.next_robot_up(position);
-        [up_wall, up_robot, Some(0)]
-            .iter()
-            .flatten()
-            .max()
-            .filter(|row| **row != position.row)
-            .map(|row| Position {
-                col: position.col,
-                row: *row,
-            })
+        let farthest = self.get_farthest_square(&position, Direction::Up);
+        let row = robot_positions
+            .next_robot_up(&position)
+            .map_or(farthest.row, |row| row.max(farthest.row));
+        (row != position.row).then_some(Position::new(row, position.col))
     }
 
     fn get_valid_down_move<P: RobotPositions>(
@@ -41,43 +155,24 @@ where
         robot_positions: &P,
     ) -> Option<Position> {
         let position = robot_positions.get_robot_position(robot);
-        let down_wall = self.wall_configuration.next_wall_down(position);
-        let down_robot = robot_positions.next_robot_down(position);
-        [
-            down_wall,
-            down_robot,
-            Some(self.wall_configuration.get_height() - 1),
-        ]
-        .iter()
-        .flatten()
-        .min()
-        .filter(|row| **row != position.row)
-        .map(|row| Position {
-            col: position.col,
-            row: *row,
-        })
+        let farthest = self.get_farthest_square(&position, Direction::Down);
+        let row = robot_positions
+            .next_robot_down(&position)
+            .map_or(farthest.row, |row| row.min(farthest.row));
+        (row != position.row).then_some(Position::new(row, position.col))
     }
+
     fn get_valid_right_move<P: RobotPositions>(
         &self,
         robot: usize,
         robot_positions: &P,
     ) -> Option<Position> {
         let position = robot_positions.get_robot_position(robot);
-        let right_wall = self.wall_configuration.next_wall_right(position);
-        let right_robot = robot_positions.next_robot_right(position);
-        [
-            right_wall,
-            right_robot,
-            Some(self.wall_configuration.get_width() - 1),
-        ]
-        .iter()
-        .flatten()
-        .min()
-        .filter(|col| **col != position.col)
-        .map(|col| Position {
-            col: *col,
-            row: position.row,
-        })
+        let farthest = self.get_farthest_square(&position, Direction::Right);
+        let col = robot_positions
+            .next_robot_right(&position)
+            .map_or(farthest.col, |col| col.min(farthest.col));
+        (col != position.col).then_some(Position::new(position.row, col))
     }
 
     fn get_valid_left_move<P: RobotPositions>(
@@ -86,22 +181,223 @@ where
         robot_positions: &P,
     ) -> Option<Position> {
         let position = robot_positions.get_robot_position(robot);
-        let left_wall = self.wall_configuration.next_wall_left(position);
-        let left_robot = robot_positions.next_robot_left(position);
-        [left_wall, left_robot, Some(0)]
-            .iter()
-            .flatten()
-            .max()
-            .filter(|col| **col != position.col)
-            .map(|col| Position {
-                col: *col,
-                row: position.row,
-            })
+        let farthest = self.get_farthest_square(&position, Direction::Left);
+        let col = robot_positions
+            .next_robot_left(&position)
+            .map_or(farthest.col, |col| col.max(farthest.col));
+        (col != position.col).then_some(Position::new(position.row, col))
     }
+
     pub fn get_valid_moves_for_robot<P: RobotPositions>(
         &self,
         robot: usize,
         robot_positions: &P,
+    ) -> Vec<(Direction, Position)> {
+        if !self.mirrors.is_empty() {
+            return self
+                .get_valid_paths_for_robot(robot, robot_positions)
+                .into_iter()
+                .flat_map(|(direction, path)| path.last().cloned().map(|end| (direction, end)))
+                .collect();
+        }
+        self.get_valid_straight_moves_for_robot(robot, robot_positions)
+    }
+
+    // Same as `get_valid_moves_for_robot`, but with the squares where the robot
+    // bounced off a mirror before the square where it stops
+    pub fn get_valid_paths_for_robot<P: RobotPositions>(
+        &self,
+        robot: usize,
+        robot_positions: &P,
+    ) -> Vec<(Direction, Vec<Position>)> {
+        if self.mirrors.is_empty() {
+            return self
+                .get_valid_straight_moves_for_robot(robot, robot_positions)
+                .into_iter()
+                .map(|(direction, position)| (direction, vec![position]))
+                .collect();
+        }
+        DIRECTIONS
+            .into_iter()
+            .flat_map(|direction| {
+                self.get_path(robot, robot_positions, direction)
+                    .map(|path| (direction, path))
+            })
+            .collect()
+    }
+
+    // Squares from which the robot would stop on its current square, with the
+    // direction of the move, the other robots staying where they are. Only
+    // straight moves are considered, so mirrors are ignored.
+    pub fn get_predecessors_for_robot<P: RobotPositions>(
+        &self,
+        robot: usize,
+        robot_positions: &P,
+    ) -> Vec<(Direction, Position)> {
+        let position = robot_positions.get_robot_position(robot);
+        let moves = self.get_valid_straight_moves_for_robot(robot, robot_positions);
+        DIRECTIONS
+            .into_iter()
+            // something has to stop the robot on its square
+            .filter(|direction| moves.iter().all(|(other, _)| other != direction))
+            .flat_map(|direction| {
+                // the robot comes from anywhere it could slide to in the
+                // opposite direction
+                let start = moves
+                    .iter()
+                    .find(|(other, _)| *other == direction.opposite())
+                    .map_or(position, |(_, start)| *start);
+                let squares: Vec<Position> = if start.row == position.row {
+                    let cols = start.col.min(position.col)..=start.col.max(position.col);
+                    cols.map(|col| Position::new(position.row, col)).collect()
+                } else {
+                    let rows = start.row.min(position.row)..=start.row.max(position.row);
+                    rows.map(|row| Position::new(row, position.col)).collect()
+                };
+                squares
+                    .into_iter()
+                    .filter(move |square| *square != position)
+                    .map(move |square| (direction, square))
+            })
+            .collect()
+    }
+
+    // Fewest moves for the robot to reach each square (indexed by
+    // `row * width + col`), the other robots staying where they are. `None`
+    // for squares it can't reach.
+    pub fn distance_map<P: RobotPositions>(
+        &self,
+        robot: usize,
+        robot_positions: &P,
+    ) -> Vec<Option<usize>> {
+        let width = self.get_width();
+        let mut distances = vec![None; self.get_height() * width];
+        let start = robot_positions.get_robot_position(robot);
+        distances[start.row * width + start.col] = Some(0);
+        let mut queue = VecDeque::from([(robot_positions.clone(), 0)]);
+        while let Some((robot_positions, moves)) = queue.pop_front() {
+            for (_, position) in self.get_valid_moves_for_robot(robot, &robot_positions) {
+                let distance = &mut distances[position.row * width + position.col];
+                if distance.is_none() {
+                    *distance = Some(moves + 1);
+                    queue.push_back((robot_positions.update(robot, position), moves + 1));
+                }
+            }
+        }
+        distances
+    }
+
+    // Same as `distance_map`, but the other robots can move too (their moves
+    // count as well). The number of placements of the robots grows quickly
+    // with the number of moves, so only the squares that can be reached in at
+    // most `max_moves` moves are found.
+    pub fn distance_map_with_moving_robots<P: RobotPositions>(
+        &self,
+        robot: usize,
+        robot_positions: &P,
+        max_moves: usize,
+    ) -> Vec<Option<usize>> {
+        let width = self.get_width();
+        let mut distances = vec![None; self.get_height() * width];
+        let start = robot_positions.get_robot_position(robot);
+        distances[start.row * width + start.col] = Some(0);
+        let mut explored = HashSet::from([robot_positions.clone()]);
+        let mut layer = vec![robot_positions.clone()];
+        for moves in 1..=max_moves {
+            let mut next_layer = vec![];
+            for robot_positions in &layer {
+                for moved_robot in 0..robot_positions.num_robots() {
+                    for (_, position) in
+                        self.get_valid_moves_for_robot(moved_robot, robot_positions)
+                    {
+                        let next_robot_positions = robot_positions.update(moved_robot, position);
+                        if !explored.insert(next_robot_positions.clone()) {
+                            continue;
+                        }
+                        let position = next_robot_positions.get_robot_position(robot);
+                        distances[position.row * width + position.col].get_or_insert(moves);
+                        next_layer.push(next_robot_positions);
+                    }
+                }
+            }
+            layer = next_layer;
+        }
+        distances
+    }
+
+    // Square next to `position`, if nothing prevents the robot from going there
+    fn get_next_square<P: RobotPositions>(
+        &self,
+        robot: usize,
+        robot_positions: &P,
+        position: &Position,
+        direction: Direction,
+    ) -> Option<Position> {
+        // the robot is stuck against a wall (or the edge of the board)
+        if self.get_farthest_square(position, direction) == *position {
+            return None;
+        }
+        let next_position = match direction {
+            Direction::Up => Position::new(position.row - 1, position.col),
+            Direction::Down => Position::new(position.row + 1, position.col),
+            Direction::Right => Position::new(position.row, position.col + 1),
+            Direction::Left => Position::new(position.row, position.col - 1),
+        };
+        let is_free = self.contains(&next_position)
+            && (0..robot_positions.num_robots()).all(|other_robot| {
+                other_robot == robot
+                    || robot_positions.get_robot_position(other_robot) != next_position
+            });
+        is_free.then_some(next_position)
+    }
+
+    // Moves the robot square by square, so that it can bounce off mirrors.
+    // Returns `None` if the robot can't move, or if it would bounce around
+    // forever.
+    fn get_path<P: RobotPositions>(
+        &self,
+        robot: usize,
+        robot_positions: &P,
+        direction: Direction,
+    ) -> Option<Vec<Position>> {
+        let start = robot_positions.get_robot_position(robot);
+        let mut position = start;
+        let mut direction = direction;
+        let mut path = vec![];
+        // a path that doesn't loop goes through each square at most once in
+        // each direction
+        let max_steps = 4 * self.get_height() * self.get_width();
+        let mut steps = 0;
+        while let Some(next_position) =
+            self.get_next_square(robot, robot_positions, &position, direction)
+        {
+            steps += 1;
+            if steps > max_steps {
+                return None;
+            }
+            position = next_position;
+            if let Some(mirror) = self.mirrors.iter().find(|m| m.position == position) {
+                let next_direction = mirror.deflect(robot, direction);
+                if next_direction != direction {
+                    path.push(position);
+                    direction = next_direction;
+                }
+            }
+        }
+        if position == start {
+            return None;
+        }
+        // the robot might stop on the last mirror it bounced off
+        if path.last() != Some(&position) {
+            path.push(position);
+        }
+        Some(path)
+    }
+
+    fn get_valid_straight_moves_for_robot<P: RobotPositions>(
+        &self,
+        robot: usize,
+        robot_positions: &P,
     ) -> Vec<(Direction, Position)> {
         [
             (
@@ -136,21 +432,27 @@ mod tests {
     use wasm_bindgen_test::wasm_bindgen_test;
 
     use crate::solver::{
-        board::Board, robot_positions::RobotPositionsVec,
-        wall_configuration::WallConfigurationVecVec, Direction, Position,
+        ascii_board::AsciiBoard,
+        board::Board,
+        error::SolveError,
+        heuristic::TargetDistances,
+        move_sequence::{MoveSequence, MoveSequenceLinkedList},
+        robot_positions::{RobotPositions, RobotPositionsVec},
+        solver,
+        wall_configuration::WallConfigurationVecVec,
+        Direction, Mirror, MirrorOrientation, Position, Target,
     };
 
     #[test]
     #[wasm_bindgen_test]
     fn test_board() {
-        let board = Board {
-            wall_configuration: WallConfigurationVecVec {
-                height: 6,
-                width: 5,
-                right_walls: vec![vec![], vec![2], vec![], vec![], vec![], vec![]],
-                bottom_walls: vec![vec![], vec![], vec![1], vec![], vec![]],
-            },
-        };
+        let board = Board::new(WallConfigurationVecVec {
+            height: 6,
+            width: 5,
+            right_walls: vec![vec![], vec![2], vec![], vec![], vec![], vec![]],
+            bottom_walls: vec![vec![], vec![], vec![1], vec![], vec![]],
+            blocked_squares: vec![],
+        });
         let robot_positions = RobotPositionsVec::new(vec![
             Position::new(0, 0),
             Position::new(1, 0),
@@ -188,4 +490,226 @@ mod tests {
             assert_eq!(result, expected)
         }
     }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_board_predecessors() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _________
+            |. . . . .|
+            |    _    |
+            |. A . .|#|
+            |         |
+            |. B . . .|
+            |_        |
+            |. . . C .|
+            |_________|
+        "
+        .parse()
+        .unwrap();
+        let board = Board::new(wall_configuration);
+        assert_eq!(
+            board.get_predecessors_for_robot(0, &robot_positions),
+            // only B stops A, when it comes down
+            vec![(Direction::Down, Position::new(0, 1))]
+        );
+
+        // a square is a predecessor if and only if a move from it stops on the
+        // square of the robot
+        let by_square = |(direction, square): &(Direction, Position)| {
+            (*direction as usize, square.row, square.col)
+        };
+        for robot in 0..3 {
+            let free_squares: Vec<Position> = (0..4)
+                .flat_map(|row| (0..5).map(move |col| Position::new(row, col)))
+                .filter(|square| {
+                    !board.is_blocked(square)
+                        && (0..3).all(|other| {
+                            other == robot || robot_positions.get_robot_position(other) != *square
+                        })
+                })
+                .collect();
+            for square in &free_squares {
+                let mut expected: Vec<(Direction, Position)> = free_squares
+                    .iter()
+                    .flat_map(|from| {
+                        board
+                            .get_valid_moves_for_robot(robot, &robot_positions.update(robot, *from))
+                            .into_iter()
+                            .filter(|(_, end)| end == square)
+                            .map(|(direction, _)| (direction, *from))
+                    })
+                    .collect();
+                expected.sort_by_key(by_square);
+                let mut predecessors = board
+                    .get_predecessors_for_robot(robot, &robot_positions.update(robot, *square));
+                predecessors.sort_by_key(by_square);
+                assert_eq!(predecessors, expected, "{:?} {:?}", robot, square);
+            }
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_board_distance_map() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _______
+            |A . . .|
+            |       |
+            |. . . B|
+            |    _  |
+            |. . . .|
+            |       |
+            |. . . .|
+            |_______|
+        "
+        .parse()
+        .unwrap();
+        let board = Board::new(wall_configuration);
+        let rows = |distances: Vec<Option<usize>>| -> Vec<Vec<Option<usize>>> {
+            distances.chunks(4).map(|row| row.to_vec()).collect()
+        };
+        assert_eq!(
+            rows(board.distance_map(0, &robot_positions)),
+            vec![
+                vec![Some(0), None, None, Some(1)],
+                vec![None, None, None, None],
+                vec![Some(4), None, None, Some(3)],
+                vec![Some(1), None, None, Some(2)],
+            ]
+        );
+        // robot 1 can move up to stop robot 0 on the third column, or down to
+        // stop it on the third row
+        assert_eq!(
+            rows(board.distance_map_with_moving_robots(0, &robot_positions, 3)),
+            vec![
+                vec![Some(0), None, Some(2), Some(1)],
+                vec![None, None, Some(3), None],
+                vec![Some(3), None, None, Some(3)],
+                vec![Some(1), None, Some(3), Some(2)],
+            ]
+        );
+        // in a single move, moving the other robots doesn't help
+        assert_eq!(
+            board.distance_map_with_moving_robots(0, &robot_positions, 1),
+            board
+                .distance_map(0, &robot_positions)
+                .into_iter()
+                .map(|distance| distance.filter(|moves| *moves <= 1))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_invalid_board() {
+        // a wall on the right of the last column
+        let board = Board::new(WallConfigurationVecVec {
+            height: 3,
+            width: 3,
+            right_walls: vec![vec![3], vec![], vec![]],
+            bottom_walls: vec![vec![], vec![], vec![]],
+            blocked_squares: vec![],
+        });
+        let robot_positions = RobotPositionsVec::new(vec![Position::new(0, 0)]);
+        assert_eq!(
+            solver::validate_robot(&board, &robot_positions, 0),
+            Err(SolveError::InvalidWallConfiguration)
+        );
+        assert_eq!(
+            solver::solve(
+                &board,
+                robot_positions.clone(),
+                MoveSequenceLinkedList::empty(),
+                Target::Robot(0, Position::new(2, 2)),
+            )
+            .err(),
+            Some(SolveError::InvalidWallConfiguration)
+        );
+        // robots don't move on it, rather than out of the board
+        assert_eq!(
+            board.get_farthest_square(&Position::new(0, 0), Direction::Right),
+            Position::new(0, 0)
+        );
+        assert!(board
+            .get_valid_moves_for_robot(0, &robot_positions)
+            .is_empty());
+        assert!(board
+            .get_predecessors_for_robot(0, &robot_positions)
+            .is_empty());
+        assert_eq!(
+            board.distance_map(0, &robot_positions),
+            [vec![Some(0)], vec![None; 8]].concat()
+        );
+        let distances = TargetDistances::new(&board, &Position::new(2, 2));
+        assert_eq!(distances.get(&Position::new(0, 0)), None);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_board_with_mirrors() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _________
+            |. . A . .|
+            |         |
+            |. . . . .|
+            |         |
+            |.|. . . .|
+            |         |
+            |. C B . .|
+            |_________|
+        "
+        .parse()
+        .unwrap();
+        let mirror = |row, col, orientation| Mirror {
+            position: Position::new(row, col),
+            orientation,
+            color: 1,
+        };
+        // the mirrors make a loop around the middle of the board
+        let board = Board::new(wall_configuration).with_mirrors(vec![
+            mirror(0, 1, MirrorOrientation::Slash),
+            mirror(0, 3, MirrorOrientation::Backslash),
+            mirror(2, 3, MirrorOrientation::Slash),
+            mirror(2, 1, MirrorOrientation::Backslash),
+        ]);
+
+        type Case = (usize, Vec<(Direction, Vec<Position>)>);
+        let cases: Vec<Case> = vec![
+            // going right or left, the robot would bounce around forever
+            (0, vec![(Direction::Down, vec![Position::new(2, 2)])]),
+            // robot 1 has the color of the mirrors, so it goes through them
+            (
+                1,
+                vec![
+                    (Direction::Up, vec![Position::new(1, 2)]),
+                    (Direction::Right, vec![Position::new(3, 4)]),
+                ],
+            ),
+            // bounces off a mirror, then stops on it because of the wall
+            (
+                2,
+                vec![
+                    (Direction::Up, vec![Position::new(2, 1)]),
+                    (Direction::Left, vec![Position::new(3, 0)]),
+                ],
+            ),
+        ];
+        for (robot, expected) in cases {
+            let result = board.get_valid_paths_for_robot(robot, &robot_positions);
+            assert_eq!(result, expected)
+        }
+    }
 }
@@ -0,0 +1,416 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    board::Board, wall_configuration::WallConfigurationVecVec, Direction, Position, Symbol,
+    TargetChip, TargetSquare,
+};
+
+// Standard boards are made of four double-sided tiles, each covering a quarter
+// of the board. Only the A sides are available for now.
+pub const QUADRANT_SIZE: usize = 8;
+pub const BOARD_SIZE: usize = 2 * QUADRANT_SIZE;
+
+// Side A of a tile, drawn as the top-left quarter of the board (the corner of
+// the central block being in the bottom-right corner). Walls follow the same
+// conventions as `WallConfigurationVecVec`.
+pub struct Tile {
+    right_walls: [&'static [usize]; QUADRANT_SIZE],
+    bottom_walls: [&'static [usize]; QUADRANT_SIZE],
+    targets: &'static [((usize, usize), TargetChip)],
+}
+
+// The A sides reproduce the board the web app has been using since its first
+// version (one tile per quadrant)
+pub const TILES: [Tile; 4] = [
+    Tile {
+        right_walls: [&[4], &[], &[6], &[], &[2], &[], &[4], &[6]],
+        bottom_walls: [&[2], &[], &[3], &[4], &[], &[5], &[2], &[6]],
+        targets: &[
+            ((2, 6), TargetChip::Colored(0, Symbol::Circle)),
+            ((4, 2), TargetChip::Colored(1, Symbol::Triangle)),
+            ((4, 3), TargetChip::Colored(2, Symbol::Square)),
+            ((6, 5), TargetChip::Colored(3, Symbol::Hexagon)),
+        ],
+    },
+    Tile {
+        right_walls: [&[2], &[], &[5], &[], &[], &[0], &[], &[4, 6]],
+        bottom_walls: [&[], &[5], &[], &[], &[6], &[2], &[1], &[6]],
+        targets: &[
+            ((2, 5), TargetChip::Colored(1, Symbol::Circle)),
+            ((2, 6), TargetChip::Colored(2, Symbol::Triangle)),
+            ((5, 1), TargetChip::Colored(3, Symbol::Square)),
+            ((7, 4), TargetChip::Colored(0, Symbol::Hexagon)),
+        ],
+    },
+    Tile {
+        right_walls: [&[4], &[], &[5], &[2], &[], &[1], &[], &[5, 6]],
+        bottom_walls: [&[6], &[5], &[2], &[3], &[], &[7], &[1], &[6]],
+        targets: &[
+            ((2, 6), TargetChip::Colored(2, Symbol::Circle)),
+            ((3, 2), TargetChip::Colored(3, Symbol::Triangle)),
+            ((3, 3), TargetChip::Vortex),
+            ((5, 1), TargetChip::Colored(0, Symbol::Square)),
+            ((7, 5), TargetChip::Colored(1, Symbol::Hexagon)),
+        ],
+    },
+    Tile {
+        right_walls: [&[5], &[], &[], &[0], &[6], &[], &[2], &[6]],
+        bottom_walls: [&[5], &[2], &[5], &[6], &[], &[], &[4], &[6]],
+        targets: &[
+            ((3, 1), TargetChip::Colored(3, Symbol::Circle)),
+            ((4, 6), TargetChip::Colored(0, Symbol::Triangle)),
+            ((6, 2), TargetChip::Colored(1, Symbol::Square)),
+            ((6, 3), TargetChip::Colored(2, Symbol::Hexagon)),
+        ],
+    },
+];
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Right,
+    Direction::Left,
+];
+
+fn wall_flag(direction: Direction) -> u8 {
+    match direction {
+        Direction::Up => 1,
+        Direction::Down => 2,
+        Direction::Right => 4,
+        Direction::Left => 8,
+    }
+}
+
+// Walls around each square of a quarter of the board, stored on both sides of
+// each wall so that the quarter can be rotated easily
+#[derive(Clone)]
+struct Quadrant {
+    walls: [[u8; QUADRANT_SIZE]; QUADRANT_SIZE],
+    targets: Vec<(Position, TargetChip)>,
+}
+
+impl Quadrant {
+    fn new(tile: &Tile) -> Self {
+        let mut walls = [[0; QUADRANT_SIZE]; QUADRANT_SIZE];
+        for (row, cols) in tile.right_walls.iter().enumerate() {
+            for col in cols.iter() {
+                walls[row][*col] |= wall_flag(Direction::Right);
+                if col + 1 < QUADRANT_SIZE {
+                    walls[row][col + 1] |= wall_flag(Direction::Left);
+                }
+            }
+        }
+        for (col, rows) in tile.bottom_walls.iter().enumerate() {
+            for row in rows.iter() {
+                walls[*row][col] |= wall_flag(Direction::Down);
+                if row + 1 < QUADRANT_SIZE {
+                    walls[row + 1][col] |= wall_flag(Direction::Up);
+                }
+            }
+        }
+        Quadrant {
+            walls,
+            targets: tile
+                .targets
+                .iter()
+                .map(|((row, col), chip)| (Position::new(*row, *col), *chip))
+                .collect(),
+        }
+    }
+
+    fn transformed(
+        &self,
+        position: impl Fn(&Position) -> Position,
+        direction: impl Fn(Direction) -> Direction,
+    ) -> Self {
+        let mut walls = [[0; QUADRANT_SIZE]; QUADRANT_SIZE];
+        for row in 0..QUADRANT_SIZE {
+            for col in 0..QUADRANT_SIZE {
+                let next_position = position(&Position::new(row, col));
+                for wall in DIRECTIONS {
+                    if self.walls[row][col] & wall_flag(wall) != 0 {
+                        walls[next_position.row][next_position.col] |= wall_flag(direction(wall));
+                    }
+                }
+            }
+        }
+        Quadrant {
+            walls,
+            targets: self
+                .targets
+                .iter()
+                .map(|(target, chip)| (position(target), *chip))
+                .collect(),
+        }
+    }
+
+    fn rotated_clockwise(&self) -> Self {
+        self.transformed(
+            |position| Position::new(position.col, QUADRANT_SIZE - 1 - position.row),
+            |direction| match direction {
+                Direction::Up => Direction::Right,
+                Direction::Right => Direction::Down,
+                Direction::Down => Direction::Left,
+                Direction::Left => Direction::Up,
+            },
+        )
+    }
+}
+
+// Tiles placed clockwise starting from the top-left quarter of the board. Each
+// tile is rotated so that its central block corner faces the center of the
+// board.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BoardLayout {
+    pub quadrants: [usize; 4],
+}
+
+impl Default for BoardLayout {
+    // Board used by the web app
+    fn default() -> Self {
+        BoardLayout {
+            quadrants: [0, 1, 2, 3],
+        }
+    }
+}
+
+impl BoardLayout {
+    // Every tile has to be used exactly once
+    pub fn is_valid(&self) -> bool {
+        let mut tiles = self.quadrants.to_vec();
+        tiles.sort();
+        tiles == (0..TILES.len()).collect::<Vec<_>>()
+    }
+
+    // All the legal layouts: any order of the tiles
+    pub fn all() -> Vec<BoardLayout> {
+        let mut orders: Vec<Vec<usize>> = vec![vec![]];
+        for _ in 0..TILES.len() {
+            orders = orders
+                .into_iter()
+                .flat_map(|order| {
+                    (0..TILES.len())
+                        .filter(|tile| !order.contains(tile))
+                        .map(|tile| [order.clone(), vec![tile]].concat())
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+        }
+        orders
+            .into_iter()
+            .map(|order| BoardLayout {
+                quadrants: [0, 1, 2, 3].map(|quadrant| order[quadrant]),
+            })
+            .collect()
+    }
+
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut tiles = [0, 1, 2, 3];
+        tiles.shuffle(rng);
+        BoardLayout { quadrants: tiles }
+    }
+
+    // Quadrants rotated and moved to their place on the board
+    fn placed_quadrants(&self) -> impl Iterator<Item = (Position, Quadrant)> + '_ {
+        let offsets = [(0, 0), (0, 1), (1, 1), (1, 0)];
+        self.quadrants
+            .iter()
+            .zip(offsets)
+            .enumerate()
+            .map(|(index, (tile, (row, col)))| {
+                let mut quadrant = Quadrant::new(&TILES[*tile]);
+                for _ in 0..index {
+                    quadrant = quadrant.rotated_clockwise();
+                }
+                let offset = Position::new(row * QUADRANT_SIZE, col * QUADRANT_SIZE);
+                (offset, quadrant)
+            })
+    }
+
+    pub fn wall_configuration(&self) -> WallConfigurationVecVec {
+        let mut walls = [[0; BOARD_SIZE]; BOARD_SIZE];
+        for (offset, quadrant) in self.placed_quadrants() {
+            for (row, quadrant_row) in quadrant.walls.iter().enumerate() {
+                for (col, square_walls) in quadrant_row.iter().enumerate() {
+                    walls[offset.row + row][offset.col + col] |= square_walls;
+                }
+            }
+        }
+        let has_wall = |row: usize, col: usize, direction: Direction| {
+            walls[row][col] & wall_flag(direction) != 0
+        };
+        WallConfigurationVecVec {
+            height: BOARD_SIZE,
+            width: BOARD_SIZE,
+            right_walls: (0..BOARD_SIZE)
+                .map(|row| {
+                    (0..BOARD_SIZE - 1)
+                        .filter(|col| {
+                            has_wall(row, *col, Direction::Right)
+                                || has_wall(row, col + 1, Direction::Left)
+                        })
+                        .collect()
+                })
+                .collect(),
+            bottom_walls: (0..BOARD_SIZE)
+                .map(|col| {
+                    (0..BOARD_SIZE - 1)
+                        .filter(|row| {
+                            has_wall(*row, col, Direction::Down)
+                                || has_wall(row + 1, col, Direction::Up)
+                        })
+                        .collect()
+                })
+                .collect(),
+            // the central block, made of the inner corner of each tile
+            blocked_squares: vec![
+                Position::new(QUADRANT_SIZE - 1, QUADRANT_SIZE - 1),
+                Position::new(QUADRANT_SIZE - 1, QUADRANT_SIZE),
+                Position::new(QUADRANT_SIZE, QUADRANT_SIZE - 1),
+                Position::new(QUADRANT_SIZE, QUADRANT_SIZE),
+            ],
+        }
+    }
+
+    // Board with the walls and the target squares of the layout
+    pub fn board(&self) -> Board<WallConfigurationVecVec> {
+        Board::new(self.wall_configuration()).with_targets(self.target_squares())
+    }
+
+    pub fn target_squares(&self) -> Vec<TargetSquare> {
+        self.placed_quadrants()
+            .flat_map(|(offset, quadrant)| {
+                quadrant
+                    .targets
+                    .into_iter()
+                    .map(move |(position, chip)| TargetSquare {
+                        position: Position::new(
+                            offset.row + position.row,
+                            offset.col + position.col,
+                        ),
+                        chip,
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use super::*;
+    use crate::solver::wall_configuration::WallConfiguration;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_default_layout() {
+        // walls of the board used by the web app
+        let expected = WallConfigurationVecVec {
+            height: 16,
+            width: 16,
+            right_walls: vec![
+                vec![4],
+                vec![9],
+                vec![6],
+                vec![],
+                vec![2, 8],
+                vec![12],
+                vec![4, 13],
+                vec![6, 8],
+                vec![6, 8, 9],
+                vec![4],
+                vec![13],
+                vec![],
+                vec![6, 12],
+                vec![5, 9],
+                vec![2],
+                vec![5, 10],
+            ],
+            bottom_walls: vec![
+                vec![2, 9],
+                vec![],
+                vec![3],
+                vec![4, 14],
+                vec![8],
+                vec![5],
+                vec![2, 12],
+                vec![6, 8],
+                vec![4, 6, 8],
+                vec![13],
+                vec![0, 7],
+                vec![],
+                vec![11],
+                vec![5, 12],
+                vec![9],
+                vec![2, 8],
+            ],
+            blocked_squares: vec![
+                Position::new(7, 7),
+                Position::new(7, 8),
+                Position::new(8, 7),
+                Position::new(8, 8),
+            ],
+        };
+        assert_eq!(BoardLayout::default().wall_configuration(), expected);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_all_layouts() {
+        let layouts = BoardLayout::all();
+        assert_eq!(layouts.len(), 24);
+        assert_eq!(layouts.iter().collect::<HashSet<_>>().len(), 24);
+
+        for layout in layouts {
+            assert!(layout.is_valid());
+            let wall_configuration = layout.wall_configuration();
+            assert!(wall_configuration.is_valid());
+
+            // central block
+            for (row, cols) in [(6, vec![7, 8]), (8, vec![7, 8])] {
+                for col in cols {
+                    assert!(wall_configuration.bottom_walls[col].contains(&row));
+                }
+            }
+            for (row, col) in [(7, 6), (7, 8), (8, 6), (8, 8)] {
+                assert!(wall_configuration.right_walls[row].contains(&col));
+            }
+
+            // 16 colored chips and the vortex, each of them in a corner
+            let targets = layout.target_squares();
+            assert_eq!(
+                targets
+                    .iter()
+                    .map(|target| target.chip)
+                    .collect::<HashSet<_>>()
+                    .len(),
+                17
+            );
+            for target in targets {
+                let Position { row, col } = target.position;
+                let vertical = (row > 0
+                    && wall_configuration.bottom_walls[col].contains(&(row - 1)))
+                    || wall_configuration.bottom_walls[col].contains(&row);
+                let horizontal = (col > 0
+                    && wall_configuration.right_walls[row].contains(&(col - 1)))
+                    || wall_configuration.right_walls[row].contains(&col);
+                assert!(vertical && horizontal, "{:?}", target);
+            }
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_random_layout() {
+        let layout = BoardLayout::random(&mut SmallRng::seed_from_u64(42));
+        assert!(layout.is_valid());
+        assert_eq!(
+            BoardLayout::random(&mut SmallRng::seed_from_u64(42)),
+            layout
+        );
+    }
+}
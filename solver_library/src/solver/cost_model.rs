@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use super::{move_sequence::Move, Target};
+
+// How solutions of the same length are ranked: each move adds a penalty, and
+// the solutions with the smallest total come first. Each field is the weight
+// of one criterion (0 to ignore it).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CostModel {
+    // the moved robot isn't the one that moved just before
+    pub robot_change: usize,
+    // the robot moves for the first time
+    pub new_robot: usize,
+    // the moved robot isn't the one that has to reach the target
+    pub other_robot: usize,
+    // the robot goes straight back in the direction it just came from
+    pub bounce_back: usize,
+}
+
+// It's better to keep moving the same robot (and not switch between robots
+// all the time)
+impl Default for CostModel {
+    fn default() -> Self {
+        CostModel {
+            robot_change: 1,
+            new_robot: 0,
+            other_robot: 0,
+            bounce_back: 0,
+        }
+    }
+}
+
+impl CostModel {
+    // Penalty of `move_`, played right after `previous_move`
+    pub fn penalty(
+        &self,
+        target: &Target,
+        previous_move: Option<&Move>,
+        new_robot: bool,
+        move_: &Move,
+    ) -> usize {
+        let same_robot = previous_move.is_some_and(|previous| previous.robot == move_.robot);
+        let mut penalty = 0;
+        if previous_move.is_some() && !same_robot {
+            penalty += self.robot_change;
+        }
+        if new_robot {
+            penalty += self.new_robot;
+        }
+        if matches!(target, Target::Robot(robot, _) if *robot != move_.robot) {
+            penalty += self.other_robot;
+        }
+        if same_robot
+            && previous_move
+                .is_some_and(|previous| previous.direction.opposite() == move_.direction)
+        {
+            penalty += self.bounce_back;
+        }
+        penalty
+    }
+
+    // Total penalty of a sequence of moves
+    pub fn total_penalty(&self, target: &Target, moves: &[Move]) -> usize {
+        moves
+            .iter()
+            .enumerate()
+            .map(|(index, move_)| {
+                let new_robot = moves[..index]
+                    .iter()
+                    .all(|other| other.robot != move_.robot);
+                self.penalty(
+                    target,
+                    index.checked_sub(1).map(|i| &moves[i]),
+                    new_robot,
+                    move_,
+                )
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{Direction, Position};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_cost_model() {
+        let target = Target::Robot(0, Position::new(0, 0));
+        let move_ = |robot, direction| Move { robot, direction };
+        let moves = [
+            move_(0, Direction::Up),
+            move_(1, Direction::Left),
+            move_(1, Direction::Right), // bounce back
+            move_(0, Direction::Down),
+            move_(2, Direction::Down),
+        ];
+        assert_eq!(CostModel::default().total_penalty(&target, &moves), 3);
+        let cost_model = CostModel {
+            robot_change: 0,
+            new_robot: 1,
+            other_robot: 10,
+            bounce_back: 100,
+        };
+        assert_eq!(cost_model.total_penalty(&target, &moves), 133);
+        assert_eq!(cost_model.total_penalty(&target, &[]), 0);
+        // no robot is worse than another for a wildcard target
+        assert_eq!(
+            cost_model.total_penalty(&Target::AnyRobot(Position::new(0, 0)), &moves),
+            103
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+
+use super::Position;
+
+// Reasons why a puzzle can't be handed over to the solver
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SolveError {
+    InvalidWallConfiguration,
+    InvalidMirror { position: Position },
+    RobotOutOfBounds { robot: usize },
+    OverlappingRobots { robot: usize, other_robot: usize },
+    RobotOnBlockedSquare { robot: usize },
+    RobotOutOfRange { robot: usize, num_robots: usize },
+    TargetOutOfBounds,
+    TargetOnBlockedSquare,
+    TargetRobotOutOfRange { robot: usize, num_robots: usize },
+    MissingTargetRobot,
+    MalformedPuzzle { message: String },
+    MalformedSolveOptions { message: String },
+    UnsupportedPuzzleVersion { version: u32 },
+    StateSpaceTooLarge { num_robots: usize },
+}
+
+impl SolveError {
+    // Machine-readable identifier of the error, stable across versions
+    pub fn code(&self) -> &'static str {
+        match self {
+            SolveError::InvalidWallConfiguration => "invalid_wall_configuration",
+            SolveError::InvalidMirror { .. } => "invalid_mirror",
+            SolveError::RobotOutOfBounds { .. } => "robot_out_of_bounds",
+            SolveError::OverlappingRobots { .. } => "overlapping_robots",
+            SolveError::RobotOnBlockedSquare { .. } => "robot_on_blocked_square",
+            SolveError::RobotOutOfRange { .. } => "robot_out_of_range",
+            SolveError::TargetOutOfBounds => "target_out_of_bounds",
+            SolveError::TargetOnBlockedSquare => "target_on_blocked_square",
+            SolveError::TargetRobotOutOfRange { .. } => "target_robot_out_of_range",
+            SolveError::MissingTargetRobot => "missing_target_robot",
+            SolveError::MalformedPuzzle { .. } => "malformed_puzzle",
+            SolveError::MalformedSolveOptions { .. } => "malformed_solve_options",
+            SolveError::UnsupportedPuzzleVersion { .. } => "unsupported_puzzle_version",
+            SolveError::StateSpaceTooLarge { .. } => "state_space_too_large",
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::InvalidWallConfiguration => {
+                write!(f, "the wall configuration doesn't match the board size")
+            }
+            SolveError::InvalidMirror { position } => write!(
+                f,
+                "the mirror at row {}, column {} is outside of the board, on a blocked square or on top of another mirror",
+                position.row, position.col
+            ),
+            SolveError::RobotOutOfBounds { robot } => {
+                write!(f, "robot {} is outside of the board", robot)
+            }
+            SolveError::OverlappingRobots { robot, other_robot } => {
+                write!(
+                    f,
+                    "robots {} and {} are on the same square",
+                    robot, other_robot
+                )
+            }
+            SolveError::RobotOnBlockedSquare { robot } => {
+                write!(f, "robot {} is on a blocked square", robot)
+            }
+            SolveError::RobotOutOfRange { robot, num_robots } => write!(
+                f,
+                "robot {} doesn't exist (there are {} robots)",
+                robot, num_robots
+            ),
+            SolveError::TargetOutOfBounds => write!(f, "the target is outside of the board"),
+            SolveError::TargetOnBlockedSquare => write!(f, "the target is on a blocked square"),
+            SolveError::TargetRobotOutOfRange { robot, num_robots } => write!(
+                f,
+                "target robot {} doesn't exist (there are {} robots)",
+                robot, num_robots
+            ),
+            SolveError::MissingTargetRobot => {
+                write!(f, "there is no robot that could reach the target")
+            }
+            SolveError::MalformedPuzzle { message } => {
+                write!(f, "the puzzle could not be read: {}", message)
+            }
+            SolveError::MalformedSolveOptions { message } => {
+                write!(f, "the solve options could not be read: {}", message)
+            }
+            SolveError::UnsupportedPuzzleVersion { version } => {
+                write!(f, "puzzles in version {} are not supported", version)
+            }
+            SolveError::StateSpaceTooLarge { num_robots } => write!(
+                f,
+                "there are too many placements of {} robots on the board to analyze them all",
+                num_robots
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
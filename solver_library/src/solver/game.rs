@@ -0,0 +1,186 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    board::Board,
+    error::SolveError,
+    move_sequence::{Move, MoveSequence, MoveSequenceLinkedList},
+    robot_positions::{RobotPositions, RobotPositionsVec},
+    solver,
+    wall_configuration::WallConfiguration,
+    Position, TargetSquare,
+};
+
+// Target drawn during a round, and the moves of the shortest solution (`None`
+// if the target can't be reached)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Round<P: RobotPositions> {
+    pub target: TargetSquare,
+    pub solution: Option<Vec<(Move, P)>>,
+}
+
+// Full game played on a board: the targets of the board are drawn one by one,
+// and the robots start each round where the previous one left them
+pub struct Game<'a, W: WallConfiguration, P: RobotPositions> {
+    board: &'a Board<W>,
+    robot_positions: P,
+    remaining_targets: Vec<TargetSquare>,
+}
+
+impl<'a, W, P> Game<'a, W, P>
+where
+    W: WallConfiguration,
+    P: RobotPositions,
+{
+    pub fn new(board: &'a Board<W>, robot_positions: P) -> Self {
+        Game {
+            board,
+            robot_positions,
+            remaining_targets: board.get_targets().to_vec(),
+        }
+    }
+
+    pub fn get_robot_positions(&self) -> &P {
+        &self.robot_positions
+    }
+
+    pub fn get_remaining_targets(&self) -> &[TargetSquare] {
+        &self.remaining_targets
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.remaining_targets.is_empty()
+    }
+
+    // Removes one of the remaining targets at random
+    pub fn draw_target<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<TargetSquare> {
+        if self.remaining_targets.is_empty() {
+            return None;
+        }
+        let index = rng.gen_range(0..self.remaining_targets.len());
+        Some(self.remaining_targets.swap_remove(index))
+    }
+
+    // Solves the target from the current positions, then moves the robots to
+    // the positions reached by the solution
+    pub fn play_target(&mut self, target: TargetSquare) -> Result<Round<P>, SolveError> {
+        self.remaining_targets.retain(|other| *other != target);
+        let solution = solver::solve(
+            self.board,
+            self.robot_positions.clone(),
+            MoveSequenceLinkedList::empty(),
+            target.target(),
+        )?
+        .solution
+        .map(|sequence| sequence.moves());
+        if let Some((_, robot_positions)) = solution.as_ref().and_then(|moves| moves.last()) {
+            self.robot_positions = robot_positions.clone();
+        }
+        Ok(Round { target, solution })
+    }
+
+    // Draws a target and plays it, or returns `None` once the game is over
+    pub fn play_round<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+    ) -> Option<Result<Round<P>, SolveError>> {
+        let target = self.draw_target(rng)?;
+        Some(self.play_target(target))
+    }
+}
+
+// Starting positions of a game: the robots are placed at random, but never on a
+// blocked square, a target or a mirror. There are fewer robots than requested
+// if the board is too small.
+pub fn random_robot_positions<W: WallConfiguration, R: Rng + ?Sized>(
+    board: &Board<W>,
+    num_robots: usize,
+    rng: &mut R,
+) -> RobotPositionsVec {
+    let free_squares: Vec<Position> = (0..board.get_height())
+        .flat_map(|row| (0..board.get_width()).map(move |col| Position::new(row, col)))
+        .filter(|position| {
+            !board.is_blocked(position)
+                && board
+                    .get_targets()
+                    .iter()
+                    .all(|target| target.position != *position)
+                && board
+                    .get_mirrors()
+                    .iter()
+                    .all(|mirror| mirror.position != *position)
+        })
+        .collect();
+    RobotPositionsVec::new(
+        free_squares
+            .choose_multiple(rng, num_robots)
+            .cloned()
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use super::*;
+    use crate::solver::{ascii_board::AsciiBoard, boards::BoardLayout, Symbol, TargetChip};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_game() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _________
+            |A . . . .|
+            |         |
+            |B . C|. D|
+            |    _    |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |_________|
+        "
+        .parse()
+        .unwrap();
+        let target = |row, col, chip| TargetSquare {
+            position: Position::new(row, col),
+            chip,
+        };
+        let board = Board::new(wall_configuration).with_targets(vec![
+            target(3, 1, TargetChip::Colored(0, Symbol::Circle)),
+            target(2, 2, TargetChip::Colored(1, Symbol::Square)),
+            target(4, 3, TargetChip::Vortex),
+        ]);
+
+        let mut game = Game::new(&board, robot_positions);
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut rounds = vec![];
+        while let Some(round) = game.play_round(&mut rng) {
+            let round = round.unwrap();
+            assert!(round.solution.is_some());
+            // the robots stay where the solution left them
+            assert!(round.target.target().is_reached(game.get_robot_positions()));
+            rounds.push(round);
+        }
+        assert!(game.is_over());
+        assert_eq!(rounds.len(), 3);
+        assert!(board
+            .get_targets()
+            .iter()
+            .all(|target| rounds.iter().any(|round| round.target == *target)));
+
+        let board = BoardLayout::default().board();
+        let robot_positions = random_robot_positions(&board, 4, &mut rng);
+        assert_eq!(robot_positions.num_robots(), 4);
+        let game = Game::new(&board, robot_positions);
+        assert_eq!(game.get_remaining_targets().len(), 17);
+    }
+}
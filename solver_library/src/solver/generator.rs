@@ -0,0 +1,170 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    board::Board,
+    error::SolveError,
+    move_sequence::{Move, MoveSequence, MoveSequenceLinkedList},
+    robot_positions::RobotPositionsVec,
+    solver::{self, SolveOptions, SolveOutcome},
+    wall_configuration::WallConfiguration,
+    Position, Target,
+};
+
+// Constraints of `generate`
+#[derive(Clone, Copy, Debug)]
+pub struct GenerateOptions {
+    pub num_robots: usize,
+    // bounds of the length of the shortest solutions, both included
+    pub min_moves: usize,
+    pub max_moves: usize,
+    // number of puzzles drawn before giving up
+    pub max_attempts: usize,
+}
+
+impl GenerateOptions {
+    pub fn new(min_moves: usize, max_moves: usize) -> Self {
+        GenerateOptions {
+            num_robots: 4,
+            min_moves,
+            max_moves,
+            max_attempts: 1000,
+        }
+    }
+}
+
+// Puzzle drawn by `generate`
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneratedPuzzle {
+    pub robot_positions: RobotPositionsVec,
+    pub target: Target,
+    // one of the shortest solutions
+    pub solution: Vec<Move>,
+}
+
+// Draws robot positions and targets at random until the shortest solutions of
+// the puzzle have between `min_moves` and `max_moves` moves. Targets are drawn
+// among the target squares of the board (if it has any that the robots can
+// reach), or anywhere otherwise. Returns `None` if no such puzzle was found in
+// `max_attempts` attempts.
+pub fn generate<W: WallConfiguration, R: Rng + ?Sized>(
+    board: &Board<W>,
+    rng: &mut R,
+    options: &GenerateOptions,
+) -> Result<Option<GeneratedPuzzle>, SolveError> {
+    let free_squares: Vec<Position> = (0..board.get_height())
+        .flat_map(|row| (0..board.get_width()).map(move |col| Position::new(row, col)))
+        .filter(|position| {
+            !board.is_blocked(position)
+                && board
+                    .get_mirrors()
+                    .iter()
+                    .all(|mirror| mirror.position != *position)
+        })
+        .collect();
+    if options.num_robots == 0
+        || free_squares.len() < options.num_robots
+        || options.min_moves > options.max_moves
+    {
+        return Ok(None);
+    }
+    let targets: Vec<Target> = board
+        .get_targets()
+        .iter()
+        .map(|target_square| target_square.target())
+        .filter(|target| match target {
+            Target::Robot(robot, _) => *robot < options.num_robots,
+            Target::AnyRobot(_) => true,
+        })
+        .collect();
+    let solve_options = SolveOptions {
+        max_moves: Some(options.max_moves),
+        ..SolveOptions::default()
+    };
+
+    for _ in 0..options.max_attempts {
+        let robot_positions = RobotPositionsVec::new(
+            free_squares
+                .choose_multiple(rng, options.num_robots)
+                .cloned()
+                .collect(),
+        );
+        let target = match targets.choose(rng) {
+            Some(target) => target.clone(),
+            None => Target::Robot(
+                rng.gen_range(0..options.num_robots),
+                *free_squares.choose(rng).unwrap(),
+            ),
+        };
+        let outcome = solver::solve_with_options(
+            board,
+            robot_positions.clone(),
+            MoveSequenceLinkedList::empty(),
+            target.clone(),
+            &solve_options,
+        )?;
+        let SolveOutcome::Solved(solutions) = outcome else {
+            continue;
+        };
+        let solution: Vec<Move> = solutions[0]
+            .moves()
+            .into_iter()
+            .map(|(move_, _)| move_)
+            .collect();
+        if solution.len() >= options.min_moves {
+            return Ok(Some(GeneratedPuzzle {
+                robot_positions,
+                target,
+                solution,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::boards::BoardLayout;
+    use rand::{rngs::SmallRng, SeedableRng};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_generate() {
+        let board = BoardLayout::default().board();
+        let options = GenerateOptions::new(5, 5);
+        let puzzle = generate(&board, &mut SmallRng::seed_from_u64(7), &options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(puzzle.solution.len(), 5);
+        assert!(board
+            .get_targets()
+            .iter()
+            .any(|t| t.target() == puzzle.target));
+        let solution = solver::solve(
+            &board,
+            puzzle.robot_positions.clone(),
+            MoveSequenceLinkedList::empty(),
+            puzzle.target.clone(),
+        )
+        .unwrap()
+        .solution
+        .unwrap();
+        assert_eq!(solution.moves().len(), 5);
+
+        // the same seed gives the same puzzle
+        assert_eq!(
+            generate(&board, &mut SmallRng::seed_from_u64(7), &options).unwrap(),
+            Some(puzzle)
+        );
+        assert_eq!(
+            generate(
+                &board,
+                &mut SmallRng::seed_from_u64(7),
+                &GenerateOptions::new(3, 2)
+            )
+            .unwrap(),
+            None
+        );
+    }
+}
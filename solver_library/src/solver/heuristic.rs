@@ -0,0 +1,122 @@
+use std::collections::VecDeque;
+
+use super::{
+    board::Board, robot_positions::RobotPositions, wall_configuration::WallConfiguration,
+    Direction, Position, Target,
+};
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Right,
+    Direction::Left,
+];
+
+// Lower bound of the number of moves needed by a robot to reach the target from
+// each square of the board, used by the A* search of `solver::solve`.
+//
+// Ignoring the other robots isn't enough to get a lower bound, since robots can
+// be used as blockers to stop in the middle of the board. Instead, robots are
+// assumed to be able to stop on any square before a wall: a real move is always
+// one of these moves, so the estimate never exceeds the real number of moves.
+pub struct TargetDistances {
+    width: usize,
+    // `None` for squares from which the target can't be reached
+    distances: Vec<Option<usize>>,
+}
+
+impl TargetDistances {
+    pub fn new<W: WallConfiguration>(board: &Board<W>, target: &Position) -> Self {
+        let width = board.get_width();
+        let mut distances = vec![None; board.get_height() * width];
+        distances[target.row * width + target.col] = Some(0);
+
+        // breadth-first search from the target: a robot sliding along a line
+        // without walls can stop on any of its squares, in both directions
+        let mut queue = VecDeque::from([(*target, 0)]);
+        while let Some((position, distance)) = queue.pop_front() {
+            for direction in DIRECTIONS {
+                let farthest = board.get_farthest_square(&position, direction);
+                let rows = position.row.min(farthest.row)..=position.row.max(farthest.row);
+                for row in rows {
+                    let cols = position.col.min(farthest.col)..=position.col.max(farthest.col);
+                    for col in cols {
+                        let square = &mut distances[row * width + col];
+                        if square.is_none() {
+                            *square = Some(distance + 1);
+                            queue.push_back((Position::new(row, col), distance + 1));
+                        }
+                    }
+                }
+            }
+        }
+        TargetDistances { width, distances }
+    }
+
+    pub fn get(&self, position: &Position) -> Option<usize> {
+        self.distances[position.row * self.width + position.col]
+    }
+
+    // Lower bound of the number of moves left to solve the puzzle, or `None` if
+    // it can't be solved anymore
+    pub fn estimate<P: RobotPositions>(
+        &self,
+        target: &Target,
+        robot_positions: &P,
+    ) -> Option<usize> {
+        match target {
+            Target::Robot(robot, _) => self.get(&robot_positions.get_robot_position(*robot)),
+            Target::AnyRobot(_) => (0..robot_positions.num_robots())
+                .flat_map(|robot| self.get(&robot_positions.get_robot_position(robot)))
+                .min(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::ascii_board::AsciiBoard;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_target_distances() {
+        let AsciiBoard {
+            wall_configuration,
+            target,
+            ..
+        } = "
+             _______
+            |. . .|.|
+            |  _    |
+            |. .|a .|
+            |    _  |
+            |. . # .|
+            |       |
+            |. . . .|
+            |_______|
+        "
+        .parse()
+        .unwrap();
+        let board = Board::new(wall_configuration);
+        let distances = TargetDistances::new(&board, target.unwrap().position());
+        let expected = [
+            [Some(2), Some(2), Some(1), Some(2)],
+            [Some(3), Some(4), Some(0), Some(1)],
+            [Some(3), Some(4), None, Some(2)],
+            [Some(3), Some(3), Some(3), Some(2)],
+        ];
+        for (row, expected_row) in expected.iter().enumerate() {
+            for (col, expected) in expected_row.iter().enumerate() {
+                assert_eq!(
+                    distances.get(&Position::new(row, col)),
+                    *expected,
+                    "{} {}",
+                    row,
+                    col
+                );
+            }
+        }
+    }
+}
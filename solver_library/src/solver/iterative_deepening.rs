@@ -0,0 +1,313 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    board::Board,
+    cost_model::CostModel,
+    error::SolveError,
+    heuristic::TargetDistances,
+    move_sequence::{Move, MoveSequence},
+    robot_positions::RobotPositions,
+    solver::{canonical_positions, validate, SequenceWithCost, Solver},
+    wall_configuration::WallConfiguration,
+    Target,
+};
+
+// Iterative deepening depth-first search: the puzzle is searched again with a
+// maximum number of moves that increases by one each time, so only the current
+// sequence of moves is kept in memory (plus a transposition table of bounded
+// size, which avoids exploring the same positions over and over). Positions
+// that can't lead to the target within the maximum number of moves according
+// to `TargetDistances` are skipped (IDA*).
+//
+// It is slower than `BestFirstSolver`, but its memory usage doesn't blow up on
+// long solutions.
+#[derive(Clone, Copy, Debug)]
+pub struct IterativeDeepeningSolver {
+    // Maximum number of positions stored in the transposition table. Once the
+    // table is full, new positions aren't remembered, and only going back to a
+    // position of the current sequence of moves is avoided. The search still
+    // ends on puzzles with no solution, but it can take exponentially longer.
+    pub max_table_size: usize,
+}
+
+impl Default for IterativeDeepeningSolver {
+    fn default() -> Self {
+        IterativeDeepeningSolver {
+            max_table_size: 1 << 20,
+        }
+    }
+}
+
+struct Search<'a, W: WallConfiguration, P: RobotPositions> {
+    board: &'a Board<W>,
+    target: &'a Target,
+    distances: Option<&'a TargetDistances>,
+    max_moves: usize,
+    max_table_size: usize,
+    // smallest number of moves after which each position was reached during
+    // the current iteration
+    table: HashMap<P, usize>,
+    // positions of the current sequence of moves
+    path: HashSet<P>,
+    // whether some positions couldn't be explored because of `max_moves`
+    cutoff: bool,
+}
+
+impl<W, P> Search<'_, W, P>
+where
+    W: WallConfiguration,
+    P: RobotPositions,
+{
+    fn search<M: MoveSequence<P>>(
+        &mut self,
+        sequence: M,
+        robot_positions: &P,
+        moves: usize,
+    ) -> Option<M> {
+        if self.target.is_reached(robot_positions) {
+            return Some(sequence);
+        }
+        let estimate = match self.distances {
+            Some(distances) => distances.estimate(self.target, robot_positions)?,
+            None => 0,
+        };
+        // going around in circles can't lead to a shorter solution, and isn't a
+        // reason to search further (only sequences without loops are, and they
+        // can't be longer than the number of positions)
+        let key = canonical_positions(self.board, self.target, robot_positions);
+        if self.path.contains(&key) {
+            return None;
+        }
+        if moves + estimate.max(1) > self.max_moves {
+            self.cutoff = true;
+            return None;
+        }
+        // already explored with at least as many moves left
+        let seen_moves = self.table.get(&key).copied();
+        if seen_moves.is_some_and(|seen_moves| seen_moves <= moves) {
+            return None;
+        }
+        if seen_moves.is_some() || self.table.len() < self.max_table_size {
+            self.table.insert(key.clone(), moves);
+        }
+
+        self.path.insert(key.clone());
+        let solution = self.search_moves(sequence, robot_positions, moves);
+        self.path.remove(&key);
+        solution
+    }
+
+    fn search_moves<M: MoveSequence<P>>(
+        &mut self,
+        sequence: M,
+        robot_positions: &P,
+        moves: usize,
+    ) -> Option<M> {
+        for robot in 0..robot_positions.num_robots() {
+            for (direction, next_position) in
+                self.board.get_valid_moves_for_robot(robot, robot_positions)
+            {
+                let next_robot_positions = robot_positions.update(robot, next_position);
+                let next_sequence =
+                    sequence.append(Move { robot, direction }, next_robot_positions.clone());
+                if let Some(solution) = self.search(next_sequence, &next_robot_positions, moves + 1)
+                {
+                    return Some(solution);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Solver for IterativeDeepeningSolver {
+    fn solve<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
+        &self,
+        board: &Board<W>,
+        robot_positions: P,
+        empty_move_sequence: M,
+        target: Target,
+    ) -> Result<Option<SequenceWithCost<P, M>>, SolveError> {
+        validate(board, &robot_positions, &target)?;
+
+        // same as in `solver::search`, mirrors make the heuristic wrong
+        let distances = board
+            .get_mirrors()
+            .is_empty()
+            .then(|| TargetDistances::new(board, target.position()));
+        for max_moves in 0.. {
+            let mut search = Search {
+                board,
+                target: &target,
+                distances: distances.as_ref(),
+                max_moves,
+                max_table_size: self.max_table_size,
+                table: HashMap::new(),
+                path: HashSet::new(),
+                cutoff: false,
+            };
+            if let Some(sequence) = search.search(empty_move_sequence.clone(), &robot_positions, 0)
+            {
+                return Ok(Some(SequenceWithCost::new(
+                    sequence,
+                    &target,
+                    &CostModel::default(),
+                )));
+            }
+            // every reachable position has been explored
+            if !search.cutoff {
+                break;
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{
+        ascii_board::AsciiBoard, move_sequence::MoveSequenceLinkedList,
+        robot_positions::RobotPositionsVec, solver::BestFirstSolver, Mirror, MirrorOrientation,
+        Position,
+    };
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_iterative_deepening_solver() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _________
+            |A . . . .|
+            |         |
+            |B . C|. D|
+            |    _    |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |_________|
+        "
+        .parse()
+        .unwrap();
+        let board = Board::new(wall_configuration);
+        let empty_move_sequence = MoveSequenceLinkedList::<RobotPositionsVec>::empty();
+        let cases = [
+            (Target::Robot(0, Position::new(0, 0)), 0),
+            (Target::Robot(0, Position::new(4, 3)), 5),
+            (Target::Robot(3, Position::new(0, 0)), 4),
+            (Target::Robot(0, Position::new(3, 1)), 8),
+            (Target::Robot(1, Position::new(2, 3)), 6),
+            (Target::AnyRobot(Position::new(3, 1)), 7),
+            (Target::AnyRobot(Position::new(3, 3)), 7),
+        ];
+        let solver = IterativeDeepeningSolver::default();
+        for (target, moves) in cases {
+            let solution = solver
+                .solve(
+                    &board,
+                    robot_positions.clone(),
+                    empty_move_sequence.clone(),
+                    target.clone(),
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(solution.moves().len(), moves, "{:?}", target);
+            let last_robot_positions = solution
+                .moves()
+                .last()
+                .map(|(_, robot_positions)| robot_positions.clone())
+                .unwrap_or(robot_positions.clone());
+            assert!(target.is_reached(&last_robot_positions));
+
+            let expected = BestFirstSolver::default()
+                .solve(
+                    &board,
+                    robot_positions.clone(),
+                    empty_move_sequence.clone(),
+                    target,
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(solution.moves().len(), expected.moves().len());
+        }
+
+        // a tiny transposition table doesn't change the solutions
+        let solution = IterativeDeepeningSolver { max_table_size: 16 }
+            .solve(
+                &board,
+                robot_positions,
+                empty_move_sequence,
+                Target::Robot(3, Position::new(0, 0)),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(solution.moves().len(), 4);
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_iterative_deepening_solver_with_no_solution() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            target,
+        } = "
+             _____
+            |A a .|
+            |     |
+            |. # .|
+            |     |
+            |. . .|
+            |_____|
+        "
+        .parse()
+        .unwrap();
+        let target = target.unwrap();
+        let board = Board::new(wall_configuration.clone());
+        // with a tiny transposition table too, and with a mirror that makes the
+        // heuristic unusable
+        let boards = [
+            Board::new(wall_configuration.clone()),
+            Board::new(wall_configuration).with_mirrors(vec![Mirror {
+                position: Position::new(2, 0),
+                orientation: MirrorOrientation::Slash,
+                color: 1,
+            }]),
+        ];
+        for board in &boards {
+            for max_table_size in [1 << 20, 2, 0] {
+                assert!(IterativeDeepeningSolver { max_table_size }
+                    .solve(
+                        board,
+                        robot_positions.clone(),
+                        MoveSequenceLinkedList::empty(),
+                        target.clone(),
+                    )
+                    .unwrap()
+                    .is_none());
+            }
+        }
+        assert_eq!(
+            IterativeDeepeningSolver::default()
+                .solve(
+                    &board,
+                    robot_positions,
+                    MoveSequenceLinkedList::empty(),
+                    Target::Robot(1, Position::new(0, 1)),
+                )
+                .err(),
+            Some(SolveError::TargetRobotOutOfRange {
+                robot: 1,
+                num_robots: 1
+            })
+        );
+    }
+}
@@ -1,10 +1,28 @@
+pub mod analysis;
+pub mod ascii_board;
+pub mod bidirectional;
 pub mod board;
+pub mod boards;
+pub mod cost_model;
+pub mod error;
+pub mod game;
+pub mod generator;
+pub mod heuristic;
+pub mod iterative_deepening;
 pub mod move_sequence;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod progress;
+pub mod puzzle;
 pub mod robot_positions;
+#[allow(clippy::module_inception)]
 pub mod solver;
 pub mod wall_configuration;
 
-#[derive(Clone, Debug, Hash, PartialEq)]
+use robot_positions::RobotPositions;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct Position {
     pub row: usize,
     pub col: usize,
@@ -23,3 +41,114 @@ pub enum Direction {
     Right,
     Left,
 }
+
+impl Direction {
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Right => Direction::Left,
+            Direction::Left => Direction::Right,
+        }
+    }
+}
+
+// Square that has to be reached to solve a puzzle
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target {
+    // Only the given robot can solve the puzzle by reaching the square
+    Robot(usize, Position),
+    // Any robot reaching the square solves the puzzle (wildcard target)
+    AnyRobot(Position),
+}
+
+impl Target {
+    pub fn position(&self) -> &Position {
+        match self {
+            Target::Robot(_, position) => position,
+            Target::AnyRobot(position) => position,
+        }
+    }
+
+    pub fn is_reached<P: RobotPositions>(&self, robot_positions: &P) -> bool {
+        match self {
+            Target::Robot(robot, position) => {
+                robot_positions.get_robot_position(*robot) == *position
+            }
+            Target::AnyRobot(position) => (0..robot_positions.num_robots())
+                .any(|robot| robot_positions.get_robot_position(robot) == *position),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Symbol {
+    Circle,
+    Triangle,
+    Square,
+    Hexagon,
+}
+
+// Chip printed on a target square. Colors are identified by the index of the
+// robot with the same color (0 is red, 1 is blue, 2 is green and 3 is yellow in
+// the web app).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TargetChip {
+    Colored(usize, Symbol),
+    // Can be reached by any robot
+    Vortex,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetSquare {
+    pub position: Position,
+    pub chip: TargetChip,
+}
+
+impl TargetSquare {
+    pub fn target(&self) -> Target {
+        match self.chip {
+            TargetChip::Colored(robot, _) => Target::Robot(robot, self.position),
+            TargetChip::Vortex => Target::AnyRobot(self.position),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MirrorOrientation {
+    // `/`, going from the bottom-left corner to the top-right corner
+    Slash,
+    // `\`, going from the top-left corner to the bottom-right corner
+    Backslash,
+}
+
+// Diagonal deflector placed on a square: robots of other colors bounce off it
+// by 90°, robots of the same color pass through
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Mirror {
+    pub position: Position,
+    pub orientation: MirrorOrientation,
+    // index of the robot with the same color
+    pub color: usize,
+}
+
+impl Mirror {
+    // Direction of a robot after entering the square of the mirror
+    pub fn deflect(&self, robot: usize, direction: Direction) -> Direction {
+        if robot == self.color {
+            return direction;
+        }
+        match (self.orientation, direction) {
+            (MirrorOrientation::Slash, Direction::Up) => Direction::Right,
+            (MirrorOrientation::Slash, Direction::Right) => Direction::Up,
+            (MirrorOrientation::Slash, Direction::Down) => Direction::Left,
+            (MirrorOrientation::Slash, Direction::Left) => Direction::Down,
+            (MirrorOrientation::Backslash, Direction::Up) => Direction::Left,
+            (MirrorOrientation::Backslash, Direction::Left) => Direction::Up,
+            (MirrorOrientation::Backslash, Direction::Down) => Direction::Right,
+            (MirrorOrientation::Backslash, Direction::Right) => Direction::Down,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::{rc::Rc, sync::Arc};
 
 use super::{robot_positions::RobotPositions, Direction};
 
@@ -97,6 +97,53 @@ impl<P: RobotPositions> MoveSequence<P> for MoveSequenceLinkedList<P> {
     }
 }
 
+// Same as MoveSequenceLinkedList, but with Arc instead of Rc so that sequences
+// can be shared between threads
+enum MoveSequenceArcLinkedListInner<P>
+where
+    P: RobotPositions,
+{
+    Nil,
+    Cons(Move, P, Arc<MoveSequenceArcLinkedListInner<P>>),
+}
+
+#[derive(Clone)]
+pub struct MoveSequenceArcLinkedList<P>(Arc<MoveSequenceArcLinkedListInner<P>>)
+where
+    P: RobotPositions;
+
+impl<P: RobotPositions> MoveSequence<P> for MoveSequenceArcLinkedList<P> {
+    fn empty() -> Self {
+        MoveSequenceArcLinkedList(Arc::new(MoveSequenceArcLinkedListInner::Nil))
+    }
+
+    fn append(&self, move_: Move, next_positions: P) -> Self {
+        MoveSequenceArcLinkedList(Arc::new(MoveSequenceArcLinkedListInner::Cons(
+            move_,
+            next_positions,
+            self.0.clone(),
+        )))
+    }
+
+    fn last(&self) -> Option<(&Move, &P)> {
+        match self.0.as_ref() {
+            MoveSequenceArcLinkedListInner::Nil => None,
+            MoveSequenceArcLinkedListInner::Cons(move_, positions, _) => Some((move_, positions)),
+        }
+    }
+
+    fn to_vec(self) -> Vec<(Move, P)> {
+        let mut result = vec![];
+        let mut current = self.0;
+        while let MoveSequenceArcLinkedListInner::Cons(move_, position, next) = current.as_ref() {
+            result.push((move_.clone(), position.clone()));
+            current = next.clone();
+        }
+        result.reverse();
+        result
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use wasm_bindgen_test::wasm_bindgen_test;
@@ -105,7 +152,7 @@ mod tests {
         move_sequence::Move, robot_positions::RobotPositionsVec, Direction, Position,
     };
 
-    use super::{MoveSequence, MoveSequenceLinkedList, MoveSequenceVec};
+    use super::{MoveSequence, MoveSequenceArcLinkedList, MoveSequenceLinkedList, MoveSequenceVec};
 
     #[test]
     #[wasm_bindgen_test]
@@ -157,5 +204,6 @@ mod tests {
         }
         helper(MoveSequenceVec::empty());
         helper(MoveSequenceLinkedList::empty());
+        helper(MoveSequenceArcLinkedList::empty());
     }
 }
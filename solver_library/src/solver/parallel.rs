@@ -0,0 +1,261 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, RandomState},
+    sync::Mutex,
+};
+
+use rayon::prelude::*;
+
+use super::{
+    board::Board,
+    cost_model::CostModel,
+    error::SolveError,
+    heuristic::TargetDistances,
+    move_sequence::{Move, MoveSequence, MoveSequenceArcLinkedList},
+    robot_positions::RobotPositions,
+    solver::{canonical_positions, validate, SequenceWithCost},
+    wall_configuration::WallConfiguration,
+    Target,
+};
+
+// Multithreaded version of `solver::solve` for native builds, finding solutions
+// just as short. Positions are explored layer by layer: all the positions with
+// the same number of moves and the same estimate (see `TargetDistances`) are
+// expanded at once by the threads of the rayon pool, in order of increasing
+// total, so that the first solution found is one of the shortest.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ParallelSolver {
+    // number of threads, all the cores by default
+    pub num_threads: Option<usize>,
+}
+
+// Fewest moves after which each position has been reached, split into shards
+// behind their own lock so that threads rarely wait on each other
+struct ShardedBestMoves<P> {
+    hasher: RandomState,
+    shards: Vec<Mutex<HashMap<P, usize>>>,
+}
+
+impl<P: RobotPositions> ShardedBestMoves<P> {
+    fn new(num_shards: usize) -> Self {
+        ShardedBestMoves {
+            hasher: RandomState::new(),
+            shards: (0..num_shards)
+                .map(|_| Mutex::new(HashMap::new()))
+                .collect(),
+        }
+    }
+
+    fn shard(&self, key: &P) -> &Mutex<HashMap<P, usize>> {
+        &self.shards[self.hasher.hash_one(key) as usize % self.shards.len()]
+    }
+
+    fn get(&self, key: &P) -> Option<usize> {
+        self.shard(key).lock().unwrap().get(key).copied()
+    }
+
+    // Returns false if the position was already reached with at most `moves`
+    fn improve(&self, key: P, moves: usize) -> bool {
+        let mut shard = self.shard(&key).lock().unwrap();
+        if shard.get(&key).is_some_and(|best| *best <= moves) {
+            return false;
+        }
+        shard.insert(key, moves);
+        true
+    }
+}
+
+type Solution<P> = SequenceWithCost<P, MoveSequenceArcLinkedList<P>>;
+
+impl ParallelSolver {
+    pub fn solve<W, P>(
+        &self,
+        board: &Board<W>,
+        robot_positions: P,
+        target: Target,
+    ) -> Result<Option<Solution<P>>, SolveError>
+    where
+        W: WallConfiguration + Sync,
+        P: RobotPositions + Send + Sync,
+    {
+        match self.num_threads {
+            Some(num_threads) => rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .expect("could not start the threads")
+                .install(|| self.search(board, robot_positions, target)),
+            None => self.search(board, robot_positions, target),
+        }
+    }
+
+    fn search<W, P>(
+        &self,
+        board: &Board<W>,
+        robot_positions: P,
+        target: Target,
+    ) -> Result<Option<Solution<P>>, SolveError>
+    where
+        W: WallConfiguration + Sync,
+        P: RobotPositions + Send + Sync,
+    {
+        validate(board, &robot_positions, &target)?;
+        let solution = |sequence| {
+            Some(SequenceWithCost::new(
+                sequence,
+                &target,
+                &CostModel::default(),
+            ))
+        };
+
+        // same as in `solver::search`, mirrors make the heuristic wrong
+        let distances = board
+            .get_mirrors()
+            .is_empty()
+            .then(|| TargetDistances::new(board, target.position()));
+        let estimate = |robot_positions: &P| match &distances {
+            Some(distances) => distances.estimate(&target, robot_positions),
+            None => Some(0),
+        };
+        let key = |robot_positions: &P| canonical_positions(board, &target, robot_positions);
+
+        let best_moves = ShardedBestMoves::new(rayon::current_num_threads() * 16);
+        best_moves.improve(key(&robot_positions), 0);
+        // layers to explore, by total (moves and estimate), then by moves
+        let mut layers = BTreeMap::new();
+        if let Some(estimate) = estimate(&robot_positions) {
+            layers.insert(
+                (estimate, 0),
+                vec![(MoveSequenceArcLinkedList::empty(), robot_positions)],
+            );
+        }
+        while let Some(((total, moves), layer)) = layers.pop_first() {
+            // the estimate is zero on the target, so the layer only holds
+            // shortest solutions
+            if let Some((sequence, _)) = layer
+                .iter()
+                .find(|(_, robot_positions)| target.is_reached(robot_positions))
+            {
+                return Ok(solution(sequence.clone()));
+            }
+            let next_layer: Vec<_> = layer
+                .into_par_iter()
+                // a shorter path to this position was found after this one
+                // was queued
+                .filter(|(_, robot_positions)| {
+                    best_moves
+                        .get(&key(robot_positions))
+                        .is_none_or(|best| best >= moves)
+                })
+                .flat_map_iter(|(sequence, robot_positions)| {
+                    let mut next = vec![];
+                    for robot in 0..robot_positions.num_robots() {
+                        for (direction, position) in
+                            board.get_valid_moves_for_robot(robot, &robot_positions)
+                        {
+                            let next_robot_positions = robot_positions.update(robot, position);
+                            // the target can't be reached anymore
+                            let Some(estimate) = estimate(&next_robot_positions) else {
+                                continue;
+                            };
+                            if best_moves.improve(key(&next_robot_positions), moves + 1) {
+                                next.push((
+                                    moves + 1 + estimate,
+                                    sequence.append(
+                                        Move { robot, direction },
+                                        next_robot_positions.clone(),
+                                    ),
+                                    next_robot_positions,
+                                ));
+                            }
+                        }
+                    }
+                    next
+                })
+                .collect();
+            for (next_total, sequence, robot_positions) in next_layer {
+                // a solution as short as the layer is just as good
+                if next_total == total && target.is_reached(&robot_positions) {
+                    return Ok(solution(sequence));
+                }
+                layers
+                    .entry((next_total, moves + 1))
+                    .or_insert_with(Vec::new)
+                    .push((sequence, robot_positions));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{
+        boards::BoardLayout,
+        move_sequence::MoveSequenceLinkedList,
+        robot_positions::{RobotPositionsPacked, RobotPositionsVec},
+        solver::{BestFirstSolver, Solver},
+        Position,
+    };
+    use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
+
+    #[test]
+    fn test_parallel_solver() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..4 {
+            let board = BoardLayout::random(&mut rng).board();
+            let free_squares: Vec<Position> = (0..board.get_height())
+                .flat_map(|row| (0..board.get_width()).map(move |col| Position::new(row, col)))
+                .filter(|position| !board.is_blocked(position))
+                .collect();
+            let positions: Vec<Position> =
+                free_squares.choose_multiple(&mut rng, 4).cloned().collect();
+            let target = board.get_targets().choose(&mut rng).unwrap().target();
+
+            let expected = BestFirstSolver::default()
+                .solve(
+                    &board,
+                    RobotPositionsVec::new(positions.clone()),
+                    MoveSequenceLinkedList::empty(),
+                    target.clone(),
+                )
+                .unwrap()
+                .map(|solution| solution.moves().len());
+            for num_threads in [Some(1), None] {
+                let solution = ParallelSolver { num_threads }
+                    .solve(
+                        &board,
+                        RobotPositionsPacked::new(
+                            &positions,
+                            board.get_height(),
+                            board.get_width(),
+                        )
+                        .unwrap(),
+                        target.clone(),
+                    )
+                    .unwrap();
+                assert_eq!(
+                    solution.as_ref().map(|solution| solution.moves().len()),
+                    expected,
+                    "{:?} {:?}",
+                    positions,
+                    target
+                );
+                // the moves have to be valid, and end on the target
+                let mut current =
+                    RobotPositionsPacked::new(&positions, board.get_height(), board.get_width())
+                        .unwrap();
+                for (move_, next) in solution
+                    .map(|solution| solution.moves())
+                    .unwrap_or_default()
+                {
+                    assert!(board
+                        .get_valid_moves_for_robot(move_.robot, &current)
+                        .contains(&(move_.direction, next.get_robot_position(move_.robot))));
+                    current = next;
+                }
+                assert_eq!(target.is_reached(&current), expected.is_some());
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+// Snapshot of a running search
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress {
+    // the search is looking for solutions with this many moves: there are no
+    // shorter ones
+    pub depth: usize,
+    pub explored_positions: usize,
+    // sequences of moves waiting to be explored
+    pub queued_sequences: usize,
+    pub elapsed: Duration,
+}
+
+// Receives the progress of a search every few thousand explored positions
+pub trait ProgressObserver {
+    fn on_progress(&self, progress: &Progress);
+}
+
+impl<F: Fn(&Progress)> ProgressObserver for F {
+    fn on_progress(&self, progress: &Progress) {
+        self(progress)
+    }
+}
+
+// Measures the time spent searching. `std::time::Instant` panics in the
+// browser, so the clock of JavaScript is used there instead.
+#[derive(Clone, Copy, Debug)]
+pub struct Stopwatch {
+    #[cfg(not(target_arch = "wasm32"))]
+    start: std::time::Instant,
+    #[cfg(target_arch = "wasm32")]
+    start_milliseconds: f64,
+}
+
+impl Stopwatch {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn start() -> Self {
+        Stopwatch {
+            start: std::time::Instant::now(),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn start() -> Self {
+        Stopwatch {
+            start_milliseconds: js_sys::Date::now(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs_f64((js_sys::Date::now() - self.start_milliseconds).max(0.0) / 1000.0)
+    }
+}
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    board::Board,
+    error::SolveError,
+    robot_positions::{RobotPositions, RobotPositionsVec},
+    wall_configuration::WallConfigurationVecVec,
+    Mirror, Position, Target,
+};
+
+// Self-contained description of a puzzle, shared by the native and WASM APIs so
+// that puzzles saved from the web app can be replayed in Rust (and vice versa).
+//
+// The JSON representation uses camelCase field names:
+//
+//   {
+//     "version": 1,
+//     "height": 16,
+//     "width": 16,
+//     "rightWalls": [[4], [9], ...],   // one list of columns per row
+//     "bottomWalls": [[2, 9], [], ...], // one list of rows per column
+//     "blockedSquares": [{ "row": 7, "col": 7 }, ...], // optional
+//     "mirrors": [                                      // optional
+//       { "position": { "row": 2, "col": 5 }, "orientation": "slash", "color": 1 },
+//       ...
+//     ],
+//     "robots": [{ "row": 4, "col": 3 }, ...],
+//     "target": { "robot": 0, "position": { "row": 1, "col": 10 } },
+//     "metadata": { "name": "..." }      // optional
+//   }
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Puzzle {
+    pub version: u32,
+    pub height: usize,
+    pub width: usize,
+    pub right_walls: Vec<Vec<usize>>,
+    pub bottom_walls: Vec<Vec<usize>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_squares: Vec<Position>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<Mirror>,
+    pub robots: Vec<Position>,
+    pub target: PuzzleTarget,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<PuzzleMetadata>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PuzzleTarget {
+    // `None` (`null` in JSON) if any robot can reach the target
+    pub robot: Option<usize>,
+    pub position: Position,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PuzzleMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl Puzzle {
+    // Bumped whenever the format changes in a way older readers can't handle
+    pub const VERSION: u32 = 1;
+
+    pub fn new<P: RobotPositions>(
+        wall_configuration: WallConfigurationVecVec,
+        robot_positions: &P,
+        target: Target,
+    ) -> Self {
+        Puzzle {
+            version: Puzzle::VERSION,
+            height: wall_configuration.height,
+            width: wall_configuration.width,
+            right_walls: wall_configuration.right_walls,
+            bottom_walls: wall_configuration.bottom_walls,
+            blocked_squares: wall_configuration.blocked_squares,
+            mirrors: vec![],
+            robots: (0..robot_positions.num_robots())
+                .map(|robot| robot_positions.get_robot_position(robot))
+                .collect(),
+            target: match target {
+                Target::Robot(robot, position) => PuzzleTarget {
+                    robot: Some(robot),
+                    position,
+                },
+                Target::AnyRobot(position) => PuzzleTarget {
+                    robot: None,
+                    position,
+                },
+            },
+            metadata: None,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SolveError> {
+        serde_json::from_str(json).map_err(|error| SolveError::MalformedPuzzle {
+            message: error.to_string(),
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Serializing puzzle")
+    }
+
+    // Splits the puzzle into the inputs expected by `solver::solve`
+    pub fn into_parts(
+        self,
+    ) -> Result<(Board<WallConfigurationVecVec>, RobotPositionsVec, Target), SolveError> {
+        if self.version != Puzzle::VERSION {
+            return Err(SolveError::UnsupportedPuzzleVersion {
+                version: self.version,
+            });
+        }
+        let board = Board::new(WallConfigurationVecVec {
+            height: self.height,
+            width: self.width,
+            right_walls: self.right_walls,
+            bottom_walls: self.bottom_walls,
+            blocked_squares: self.blocked_squares,
+        })
+        .with_mirrors(self.mirrors);
+        let target = match self.target.robot {
+            Some(robot) => Target::Robot(robot, self.target.position),
+            None => Target::AnyRobot(self.target.position),
+        };
+        Ok((board, RobotPositionsVec::new(self.robots), target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{
+        move_sequence::{MoveSequence, MoveSequenceLinkedList},
+        solver,
+    };
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_puzzle() {
+        // as exported by the web app
+        let json = r#"{
+            "version": 1,
+            "height": 6,
+            "width": 5,
+            "rightWalls": [[], [2], [], [], [], []],
+            "bottomWalls": [[], [], [1], [], []],
+            "robots": [
+                { "row": 0, "col": 0 },
+                { "row": 1, "col": 0 },
+                { "row": 1, "col": 2 },
+                { "row": 1, "col": 4 }
+            ],
+            "target": { "robot": null, "position": { "row": 3, "col": 1 } },
+            "metadata": { "name": "wildcard" }
+        }"#;
+        let puzzle = Puzzle::from_json(json).unwrap();
+        assert_eq!(
+            puzzle.metadata.as_ref().and_then(|m| m.name.as_deref()),
+            Some("wildcard")
+        );
+        assert_eq!(Puzzle::from_json(&puzzle.to_json()).unwrap(), puzzle);
+
+        let (board, robot_positions, target) = puzzle.clone().into_parts().unwrap();
+        assert_eq!(target, Target::AnyRobot(Position::new(3, 1)));
+        let solution = solver::solve(
+            &board,
+            robot_positions.clone(),
+            MoveSequenceLinkedList::empty(),
+            target.clone(),
+        )
+        .unwrap()
+        .solution
+        .unwrap();
+        assert_eq!(solution.moves().len(), 7);
+
+        let wall_configuration = WallConfigurationVecVec {
+            height: 6,
+            width: 5,
+            right_walls: puzzle.right_walls.clone(),
+            bottom_walls: puzzle.bottom_walls.clone(),
+            blocked_squares: vec![],
+        };
+        assert_eq!(
+            Puzzle {
+                metadata: puzzle.metadata.clone(),
+                ..Puzzle::new(wall_configuration, &robot_positions, target)
+            },
+            puzzle
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_puzzle_errors() {
+        assert!(matches!(
+            Puzzle::from_json(r#"{ "version": 1 }"#),
+            Err(SolveError::MalformedPuzzle { .. })
+        ));
+
+        let puzzle = Puzzle {
+            version: 2,
+            ..Puzzle::new(
+                WallConfigurationVecVec {
+                    height: 1,
+                    width: 1,
+                    right_walls: vec![vec![]],
+                    bottom_walls: vec![vec![]],
+                    blocked_squares: vec![],
+                },
+                &RobotPositionsVec::new(vec![]),
+                Target::AnyRobot(Position::new(0, 0)),
+            )
+        };
+        assert_eq!(
+            puzzle.into_parts().err(),
+            Some(SolveError::UnsupportedPuzzleVersion { version: 2 })
+        );
+    }
+}
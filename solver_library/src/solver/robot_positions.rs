@@ -7,7 +7,7 @@ pub trait RobotPositions
 where
     Self: Clone + std::fmt::Debug + Eq + Hash,
 {
-    fn get_robot_position(&self, robot: usize) -> &Position;
+    fn get_robot_position(&self, robot: usize) -> Position;
     fn num_robots(&self) -> usize;
     fn update(&self, robot: usize, position: Position) -> Self;
 
@@ -16,6 +16,26 @@ where
     fn next_robot_down(&self, position: &Position) -> Option<usize>;
     fn next_robot_right(&self, position: &Position) -> Option<usize>;
     fn next_robot_left(&self, position: &Position) -> Option<usize>;
+
+    // Same positions with the robots sorted by square, except for `fixed_robot`
+    // which stays where it is. Positions that only differ by swapping the
+    // sorted robots have the same canonical positions.
+    fn canonical(&self, fixed_robot: Option<usize>) -> Self {
+        let robots: Vec<usize> = (0..self.num_robots())
+            .filter(|robot| Some(*robot) != fixed_robot)
+            .collect();
+        let mut squares: Vec<Position> = robots
+            .iter()
+            .map(|robot| self.get_robot_position(*robot))
+            .collect();
+        squares.sort_by_key(|square| (square.row, square.col));
+        robots
+            .into_iter()
+            .zip(squares)
+            .fold(self.clone(), |canonical, (robot, square)| {
+                canonical.update(robot, square)
+            })
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -37,8 +57,8 @@ impl Hash for RobotPositionsVec {
     }
 }
 impl RobotPositions for RobotPositionsVec {
-    fn get_robot_position(&self, robot: usize) -> &Position {
-        self.positions.get(robot).unwrap()
+    fn get_robot_position(&self, robot: usize) -> Position {
+        self.positions[robot]
     }
 
     fn num_robots(&self) -> usize {
@@ -99,8 +119,118 @@ impl RobotPositions for RobotPositionsVec {
     }
 }
 
+// Maximum number of robots of a `RobotPositionsPacked`
+pub const MAX_PACKED_ROBOTS: usize = 8;
+
+// Compact container storing each robot in a single byte (row in the high
+// nibble, column in the low one), so that it is cheap to copy, hash and
+// compare. Only fits boards of at most 16x16 squares.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RobotPositionsPacked {
+    squares: [u8; MAX_PACKED_ROBOTS],
+    num_robots: u8,
+}
+
+impl RobotPositionsPacked {
+    // Robots on a board of `height` by `width` squares. Returns `None` if there
+    // are too many robots, if a robot is outside of the board, or if the board
+    // is larger than the 16x16 squares that can be packed (the robots could
+    // move out of them).
+    pub fn new(positions: &[Position], height: usize, width: usize) -> Option<Self> {
+        if positions.len() > MAX_PACKED_ROBOTS || height > 16 || width > 16 {
+            return None;
+        }
+        let mut squares = [0; MAX_PACKED_ROBOTS];
+        for (square, position) in squares.iter_mut().zip(positions) {
+            if position.row >= height || position.col >= width {
+                return None;
+            }
+            *square = RobotPositionsPacked::pack(position)?;
+        }
+        Some(RobotPositionsPacked {
+            squares,
+            num_robots: positions.len() as u8,
+        })
+    }
+
+    fn pack(position: &Position) -> Option<u8> {
+        (position.row < 16 && position.col < 16).then_some((position.row << 4 | position.col) as u8)
+    }
+
+    fn unpack(square: u8) -> Position {
+        Position::new((square >> 4) as usize, (square & 0xf) as usize)
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.squares[..self.num_robots as usize]
+            .iter()
+            .map(|square| RobotPositionsPacked::unpack(*square))
+    }
+}
+
+impl RobotPositions for RobotPositionsPacked {
+    fn get_robot_position(&self, robot: usize) -> Position {
+        assert!(robot < self.num_robots as usize);
+        RobotPositionsPacked::unpack(self.squares[robot])
+    }
+
+    fn num_robots(&self) -> usize {
+        self.num_robots as usize
+    }
+
+    fn update(&self, robot: usize, position: Position) -> Self {
+        assert!(robot < self.num_robots as usize);
+        let mut updated = *self;
+        updated.squares[robot] =
+            RobotPositionsPacked::pack(&position).expect("robots stay on the board");
+        updated
+    }
+
+    fn next_robot_up(&self, position: &Position) -> Option<usize> {
+        self.positions()
+            .filter(|other_position| {
+                other_position.col == position.col && other_position.row < position.row
+            })
+            .map(|other_position| other_position.row)
+            .max()
+            .map(|row| row + 1)
+    }
+
+    fn next_robot_down(&self, position: &Position) -> Option<usize> {
+        self.positions()
+            .filter(|other_position| {
+                other_position.col == position.col && other_position.row > position.row
+            })
+            .map(|other_position| other_position.row)
+            .min()
+            .map(|row| row - 1)
+    }
+
+    fn next_robot_right(&self, position: &Position) -> Option<usize> {
+        self.positions()
+            .filter(|other_position| {
+                other_position.row == position.row && other_position.col > position.col
+            })
+            .map(|other_position| other_position.col)
+            .min()
+            .map(|col| col - 1)
+    }
+
+    fn next_robot_left(&self, position: &Position) -> Option<usize> {
+        self.positions()
+            .filter(|other_position| {
+                other_position.row == position.row && other_position.col < position.col
+            })
+            .map(|other_position| other_position.col)
+            .max()
+            .map(|col| col + 1)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
     use super::*;
     use wasm_bindgen_test::wasm_bindgen_test;
 
@@ -136,5 +266,80 @@ mod tests {
             assert_eq!(robot_positions.next_robot_right(&position), right);
             assert_eq!(robot_positions.next_robot_left(&position), left);
         }
+
+        let canonical = RobotPositionsVec::new(vec![
+            Position::new(0, 0),
+            Position::new(0, 2),
+            Position::new(0, 3),
+            Position::new(3, 0),
+        ]);
+        assert_eq!(robot_positions.canonical(None), canonical);
+        let swapped = robot_positions
+            .update(0, Position::new(0, 2))
+            .update(2, Position::new(0, 0));
+        assert_eq!(
+            swapped.canonical(Some(1)),
+            robot_positions.canonical(Some(1))
+        );
+        assert_ne!(
+            swapped.canonical(Some(0)),
+            robot_positions.canonical(Some(0))
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_robot_positions_packed() {
+        assert!(RobotPositionsPacked::new(&[Position::new(16, 0)], 17, 16).is_none());
+        assert!(RobotPositionsPacked::new(&[Position::new(0, 0)], 16, 17).is_none());
+        assert!(RobotPositionsPacked::new(&[Position::new(3, 0)], 3, 3).is_none());
+        assert!(
+            RobotPositionsPacked::new(&[Position::new(0, 0); MAX_PACKED_ROBOTS + 1], 16, 16)
+                .is_none()
+        );
+
+        // both implementations have to agree everywhere on the board
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let positions: Vec<Position> = (0..4)
+                .map(|_| Position::new(rng.gen_range(0..16), rng.gen_range(0..16)))
+                .collect();
+            let robot_positions = RobotPositionsVec::new(positions.clone());
+            let packed = RobotPositionsPacked::new(&positions, 16, 16).unwrap();
+            assert_eq!(packed.num_robots(), 4);
+            for robot in 0..4 {
+                assert_eq!(
+                    packed.get_robot_position(robot),
+                    robot_positions.get_robot_position(robot)
+                );
+            }
+            for row in 0..16 {
+                for col in 0..16 {
+                    let position = Position::new(row, col);
+                    assert_eq!(
+                        packed.next_robot_up(&position),
+                        robot_positions.next_robot_up(&position)
+                    );
+                    assert_eq!(
+                        packed.next_robot_down(&position),
+                        robot_positions.next_robot_down(&position)
+                    );
+                    assert_eq!(
+                        packed.next_robot_right(&position),
+                        robot_positions.next_robot_right(&position)
+                    );
+                    assert_eq!(
+                        packed.next_robot_left(&position),
+                        robot_positions.next_robot_left(&position)
+                    );
+                }
+            }
+            let position = Position::new(15, 15);
+            assert_eq!(
+                packed.update(2, position),
+                RobotPositionsPacked::new(&robot_positions.update(2, position).positions, 16, 16)
+                    .unwrap()
+            );
+        }
     }
 }
@@ -1,20 +1,32 @@
 use std::{
-    collections::{BinaryHeap, HashSet},
+    collections::{BinaryHeap, HashMap},
     marker::PhantomData,
+    time::Duration,
 };
 
+use serde::Serialize;
+
 use super::{
     board::Board,
+    cost_model::CostModel,
+    error::SolveError,
+    heuristic::TargetDistances,
     move_sequence::{Move, MoveSequence},
+    progress::{Progress, ProgressObserver, Stopwatch},
     robot_positions::RobotPositions,
     wall_configuration::WallConfiguration,
-    Position,
+    Target,
 };
 
+fn robot_bit(robot: usize) -> u64 {
+    1u64.checked_shl(robot as u32).unwrap_or(0)
+}
+
 #[derive(Eq, PartialEq)]
 struct Cost {
     moves: usize,
-    robot_change: usize, // it's better to continuously move the same robot if possible (and not switch between robots all the time)
+    estimate: usize, // lower bound of the number of moves left, see `TargetDistances`
+    penalty: usize,  // ranks solutions of the same length, see `CostModel`
 }
 
 impl Ord for Cost {
@@ -22,10 +34,9 @@ impl Ord for Cost {
         // XXX: BinaryHeap is a max-heap, and this cannot be changed.
         //  We therefore have to flip the ordering itself, hence why `self` and
         // `other` are reversed here.
-        other
-            .moves
-            .cmp(&self.moves)
-            .then_with(|| other.robot_change.cmp(&self.robot_change))
+        (other.moves + other.estimate)
+            .cmp(&(self.moves + self.estimate))
+            .then_with(|| other.penalty.cmp(&self.penalty))
     }
 }
 impl PartialOrd for Cost {
@@ -41,6 +52,9 @@ where
 {
     move_sequence: M,
     cost: Cost,
+    // robots moved so far, one bit per robot (robots past the 64th are never
+    // considered new)
+    robots_moved: u64,
 
     // We are only using the type parameter P as a constraint on the type parameter
     // M, which leads the compiler to believe it's unused by this type.
@@ -54,9 +68,36 @@ where
     P: RobotPositions,
     M: MoveSequence<P>,
 {
+    // Wraps a sequence found by another search algorithm
+    pub(crate) fn new(move_sequence: M, target: &Target, cost_model: &CostModel) -> Self {
+        let moves: Vec<Move> = move_sequence
+            .clone()
+            .to_vec()
+            .into_iter()
+            .map(|(move_, _)| move_)
+            .collect();
+        SequenceWithCost {
+            move_sequence,
+            cost: Cost {
+                moves: moves.len(),
+                estimate: 0,
+                penalty: cost_model.total_penalty(target, &moves),
+            },
+            robots_moved: moves.iter().fold(0, |robots_moved, move_| {
+                robots_moved | robot_bit(move_.robot)
+            }),
+            phantom_position: PhantomData,
+        }
+    }
+
     pub fn moves(&self) -> Vec<(Move, P)> {
         self.move_sequence.clone().to_vec()
     }
+
+    // Total penalty of the moves according to the cost model of the search
+    pub fn penalty(&self) -> usize {
+        self.cost.penalty
+    }
 }
 
 impl<P, M> PartialEq for SequenceWithCost<P, M>
@@ -95,111 +136,705 @@ where
     }
 }
 
-pub fn solve<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
+// Search algorithm finding one of the shortest solutions of a puzzle
+pub trait Solver {
+    fn solve<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
+        &self,
+        board: &Board<W>,
+        robot_positions: P,
+        empty_move_sequence: M,
+        target: Target,
+    ) -> Result<Option<SequenceWithCost<P, M>>, SolveError>;
+}
+
+// Best-first search of `solve`: fast, but it keeps every explored position in
+// memory
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BestFirstSolver {
+    pub cost_model: CostModel,
+}
+
+impl Solver for BestFirstSolver {
+    fn solve<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
+        &self,
+        board: &Board<W>,
+        robot_positions: P,
+        empty_move_sequence: M,
+        target: Target,
+    ) -> Result<Option<SequenceWithCost<P, M>>, SolveError> {
+        solve_with_cost_model(
+            board,
+            robot_positions,
+            empty_move_sequence,
+            target,
+            &self.cost_model,
+        )
+    }
+}
+
+// Checks that the puzzle is well-formed, so that the search itself never has to
+// deal with out of bounds positions
+pub fn validate<W: WallConfiguration, P: RobotPositions>(
+    board: &Board<W>,
+    robot_positions: &P,
+    target: &Target,
+) -> Result<(), SolveError> {
+    validate_robots(board, robot_positions)?;
+    let num_robots = robot_positions.num_robots();
+    if !board.contains(target.position()) {
+        return Err(SolveError::TargetOutOfBounds);
+    }
+    if board.is_blocked(target.position()) {
+        return Err(SolveError::TargetOnBlockedSquare);
+    }
+    match target {
+        Target::Robot(robot, _) if *robot >= num_robots => Err(SolveError::TargetRobotOutOfRange {
+            robot: *robot,
+            num_robots,
+        }),
+        Target::AnyRobot(_) if num_robots == 0 => Err(SolveError::MissingTargetRobot),
+        _ => Ok(()),
+    }
+}
+
+// Same as `validate` for the moves of `robot` alone, whatever the target (see
+// `Board::distance_map`)
+pub fn validate_robot<W: WallConfiguration, P: RobotPositions>(
+    board: &Board<W>,
+    robot_positions: &P,
+    robot: usize,
+) -> Result<(), SolveError> {
+    validate_robots(board, robot_positions)?;
+    let num_robots = robot_positions.num_robots();
+    if robot >= num_robots {
+        return Err(SolveError::RobotOutOfRange { robot, num_robots });
+    }
+    Ok(())
+}
+
+// Checks the board and the robots, but not the target
+fn validate_robots<W: WallConfiguration, P: RobotPositions>(
+    board: &Board<W>,
+    robot_positions: &P,
+) -> Result<(), SolveError> {
+    if !board.is_valid() {
+        return Err(SolveError::InvalidWallConfiguration);
+    }
+    let mirrors = board.get_mirrors();
+    for (index, mirror) in mirrors.iter().enumerate() {
+        if !board.contains(&mirror.position)
+            || board.is_blocked(&mirror.position)
+            || mirrors[..index]
+                .iter()
+                .any(|other| other.position == mirror.position)
+        {
+            return Err(SolveError::InvalidMirror {
+                position: mirror.position,
+            });
+        }
+    }
+    let num_robots = robot_positions.num_robots();
+    for robot in 0..num_robots {
+        let position = robot_positions.get_robot_position(robot);
+        if !board.contains(&position) {
+            return Err(SolveError::RobotOutOfBounds { robot });
+        }
+        if board.is_blocked(&position) {
+            return Err(SolveError::RobotOnBlockedSquare { robot });
+        }
+        if let Some(other_robot) = (0..robot)
+            .find(|other_robot| robot_positions.get_robot_position(*other_robot) == position)
+        {
+            return Err(SolveError::OverlappingRobots {
+                robot: other_robot,
+                other_robot: robot,
+            });
+        }
+    }
+    Ok(())
+}
+
+// Statistics about a search, for diagnostics and benchmarks
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SolveStats {
+    // distinct positions reached
+    pub explored_positions: usize,
+    // sequences of moves queued, including the ones reaching a position again
+    pub generated_sequences: usize,
+    // largest number of sequences waiting in the queue at once
+    pub max_queued_sequences: usize,
+    // number of moves of the solutions looked for when the search stopped
+    pub depth: usize,
+    pub duration: Duration,
+}
+
+// Outcome of `search` and `solve`
+pub struct SearchResult<P, M>
+where
+    P: RobotPositions,
+    M: MoveSequence<P>,
+{
+    pub solution: Option<SequenceWithCost<P, M>>,
+    pub stats: SolveStats,
+}
+
+// Key under which explored positions are remembered. Robots that play the same
+// role are interchangeable: positions that only differ by swapping them lead to
+// the same solutions, so they are explored once. Mirrors let some robots
+// through, which makes every robot unique.
+pub(crate) fn canonical_positions<W: WallConfiguration, P: RobotPositions>(
+    board: &Board<W>,
+    target: &Target,
+    robot_positions: &P,
+) -> P {
+    if !board.get_mirrors().is_empty() {
+        return robot_positions.clone();
+    }
+    match target {
+        Target::Robot(robot, _) => robot_positions.canonical(Some(*robot)),
+        Target::AnyRobot(_) => robot_positions.canonical(None),
+    }
+}
+
+// A* search, which is a plain best-first search when `use_heuristic` is false
+pub fn search<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
     board: &Board<W>,
     robot_positions: P,
     empty_move_sequence: M,
-    target: (usize, Position),
-) -> Option<SequenceWithCost<P, M>> {
-    let mut seen = HashSet::new();
-    seen.insert(robot_positions.clone());
+    target: Target,
+    use_heuristic: bool,
+) -> Result<SearchResult<P, M>, SolveError> {
+    let key_target = target.clone();
+    search_by_key(
+        board,
+        robot_positions,
+        empty_move_sequence,
+        target,
+        use_heuristic,
+        |robot_positions| canonical_positions(board, &key_target, robot_positions),
+    )
+}
 
-    let mut queue = BinaryHeap::new();
-    queue.push(SequenceWithCost {
-        cost: Cost {
+// Same as `search`, with positions remembered under `key(positions)`
+fn search_by_key<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
+    board: &Board<W>,
+    robot_positions: P,
+    empty_move_sequence: M,
+    target: Target,
+    use_heuristic: bool,
+    key: impl Fn(&P) -> P,
+) -> Result<SearchResult<P, M>, SolveError> {
+    let mut output = search_solutions(
+        board,
+        robot_positions,
+        empty_move_sequence,
+        target,
+        SearchSettings {
+            use_heuristic,
+            key,
+            options: &SolveOptions::default(),
+        },
+    )?;
+    Ok(SearchResult {
+        solution: output.solutions.pop(),
+        stats: output.stats,
+    })
+}
+
+// Number of positions explored between two calls to `should_cancel`
+const CANCEL_CHECK_INTERVAL: usize = 1024;
+
+// Number of positions explored between two progress reports
+const PROGRESS_INTERVAL: usize = 1 << 14;
+
+// Limits of `solve_with_options`, which are all off by default
+#[derive(Clone, Copy)]
+pub struct SolveOptions<'a> {
+    // solutions with more moves aren't looked for
+    pub max_moves: Option<usize>,
+    // the search gives up after exploring this many positions
+    pub max_explored_positions: Option<usize>,
+    // called every few explored positions, the search gives up once it
+    // returns true
+    pub should_cancel: Option<&'a dyn Fn() -> bool>,
+    // notified every few thousand explored positions
+    pub progress_observer: Option<&'a dyn ProgressObserver>,
+    // number of shortest solutions to look for (`None` for all of them)
+    pub max_solutions: Option<usize>,
+    pub cost_model: CostModel,
+}
+
+impl Default for SolveOptions<'_> {
+    fn default() -> Self {
+        SolveOptions {
+            max_moves: None,
+            max_explored_positions: None,
+            should_cancel: None,
+            progress_observer: None,
+            max_solutions: Some(1),
+            cost_model: CostModel::default(),
+        }
+    }
+}
+
+// Limit of `SolveOptions` that stopped a search
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchLimit {
+    MaxMoves,
+    MaxExploredPositions,
+    Cancelled,
+}
+
+// Outcome of `solve_with_options`
+pub enum SolveOutcome<P, M>
+where
+    P: RobotPositions,
+    M: MoveSequence<P>,
+{
+    // shortest solutions, the ones with the smallest penalty first
+    Solved(Vec<SequenceWithCost<P, M>>),
+    // every reachable position was explored
+    NoSolution,
+    // the search gave up before finding out whether there is a solution
+    LimitReached(SearchLimit),
+}
+
+// Knobs of `search_solutions`
+struct SearchSettings<'a, K> {
+    use_heuristic: bool,
+    // key under which explored positions are remembered
+    key: K,
+    options: &'a SolveOptions<'a>,
+}
+
+struct SearchOutput<P, M>
+where
+    P: RobotPositions,
+    M: MoveSequence<P>,
+{
+    solutions: Vec<SequenceWithCost<P, M>>,
+    stats: SolveStats,
+    // set when the search gave up without finding any solution
+    limit: Option<SearchLimit>,
+}
+
+// Cheapest sequence of moves found so far to reach a position
+struct Reached<P> {
+    moves: usize,
+    penalty: usize,
+    // the position itself, the key only gives it up to the order of
+    // interchangeable robots
+    robot_positions: P,
+    last_move: Option<Move>,
+    robots_moved: u64,
+}
+
+impl<P: RobotPositions> Reached<P> {
+    // Robot of this sequence standing where `robot` stands after `other`
+    fn same_robot(&self, other: &Reached<P>, robot: usize) -> usize {
+        let position = other.robot_positions.get_robot_position(robot);
+        (0..self.robot_positions.num_robots())
+            .find(|same_robot| self.robot_positions.get_robot_position(*same_robot) == position)
+            .unwrap_or(robot)
+    }
+
+    // Whether any sequence of moves played after `other` could be played after
+    // this one for a penalty at least as small, in which case there is no
+    // need to go further from `other`. The next move can cost more after a
+    // different move, and robots moved by `other` but not by this one can cost
+    // more when they move for the first time.
+    fn dominates(&self, other: &Reached<P>, cost_model: &CostModel) -> bool {
+        if self.moves > other.moves || self.penalty > other.penalty {
+            return false;
+        }
+        let same_last_robot = match (&self.last_move, &other.last_move) {
+            (Some(last_move), Some(other_last_move)) => {
+                last_move.robot == self.same_robot(other, other_last_move.robot)
+            }
+            _ => false,
+        };
+        let same_last_direction = same_last_robot
+            && self.last_move.as_ref().map(|last_move| last_move.direction)
+                == other
+                    .last_move
+                    .as_ref()
+                    .map(|last_move| last_move.direction);
+        let mut extra_penalty = (if same_last_robot {
+            0
+        } else {
+            cost_model.robot_change
+        })
+        .max(if same_last_direction {
+            0
+        } else {
+            cost_model.bounce_back
+        });
+        extra_penalty += cost_model.new_robot
+            * (0..other.robot_positions.num_robots())
+                .filter(|robot| {
+                    other.robots_moved & robot_bit(*robot) != 0
+                        && self.robots_moved & robot_bit(self.same_robot(other, *robot)) == 0
+                })
+                .count();
+        self.penalty + extra_penalty <= other.penalty
+    }
+}
+
+// Shortest solutions, the ones with the smallest penalty first, and at most
+// `max_solutions` of them. When looking for more than one solution, moves that
+// reach an explored position again with as many moves are followed too, since
+// they lead to other solutions. When looking for a single one with a cost
+// model other than the default one, they are only followed if they could lead
+// to a solution with a smaller penalty (the penalty depends on the moves played
+// before, not only on the position). With the default one, they never are:
+// this is much faster, but the solution found doesn't always have the smallest
+// penalty.
+fn search_solutions<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
+    board: &Board<W>,
+    robot_positions: P,
+    empty_move_sequence: M,
+    target: Target,
+    settings: SearchSettings<impl Fn(&P) -> P>,
+) -> Result<SearchOutput<P, M>, SolveError> {
+    let SearchSettings {
+        use_heuristic,
+        key,
+        options,
+    } = settings;
+    let max_solutions = options.max_solutions.unwrap_or(usize::MAX);
+    let cost_model = &options.cost_model;
+    let smallest_penalty = *cost_model != CostModel::default();
+    validate(board, &robot_positions, &target)?;
+
+    // the heuristic assumes that robots move in straight lines, which isn't
+    // the case anymore with mirrors
+    let distances = (use_heuristic && board.get_mirrors().is_empty())
+        .then(|| TargetDistances::new(board, target.position()));
+    let estimate = |robot_positions: &P| match &distances {
+        Some(distances) => distances.estimate(&target, robot_positions),
+        None => Some(0),
+    };
+
+    // shortest (then cheapest) sequence of moves reaching each position so far
+    let mut best_moves = HashMap::new();
+    best_moves.insert(
+        key(&robot_positions),
+        Reached {
             moves: 0,
-            robot_change: 0,
+            penalty: 0,
+            robot_positions: robot_positions.clone(),
+            last_move: None,
+            robots_moved: 0,
         },
-        move_sequence: empty_move_sequence,
-        phantom_position: PhantomData,
-    });
-    while let Some(sequence) = queue.pop() {
+    );
+
+    // whether a position was skipped because of `max_moves`
+    let mut beyond_max_moves = false;
+    let mut within_max_moves = |moves: usize, estimate: usize| {
+        let within = options.max_moves.is_none_or(|max| moves + estimate <= max);
+        beyond_max_moves |= !within;
+        within
+    };
+
+    let stopwatch = Stopwatch::start();
+    let mut stats = SolveStats::default();
+    let mut queue = BinaryHeap::new();
+    if let Some(estimate) = estimate(&robot_positions).filter(|e| within_max_moves(0, *e)) {
+        stats.generated_sequences += 1;
+        stats.max_queued_sequences = 1;
+        queue.push(SequenceWithCost {
+            cost: Cost {
+                moves: 0,
+                estimate,
+                penalty: 0,
+            },
+            move_sequence: empty_move_sequence,
+            robots_moved: 0,
+            phantom_position: PhantomData,
+        });
+    }
+    let mut solutions: Vec<SequenceWithCost<P, M>> = vec![];
+    let mut limit = None;
+    let mut popped = 0;
+    while solutions.len() < max_solutions {
+        if popped % CANCEL_CHECK_INTERVAL == 0
+            && options
+                .should_cancel
+                .is_some_and(|should_cancel| should_cancel())
+        {
+            limit = Some(SearchLimit::Cancelled);
+            break;
+        }
+        if options
+            .max_explored_positions
+            .is_some_and(|max| best_moves.len() >= max)
+        {
+            limit = Some(SearchLimit::MaxExploredPositions);
+            break;
+        }
+        let Some(sequence) = queue.pop() else {
+            break;
+        };
+        stats.depth = sequence.cost.moves + sequence.cost.estimate;
+        if let Some(observer) = options
+            .progress_observer
+            .filter(|_| popped % PROGRESS_INTERVAL == 0)
+        {
+            observer.on_progress(&Progress {
+                depth: stats.depth,
+                explored_positions: best_moves.len(),
+                queued_sequences: queue.len(),
+                elapsed: stopwatch.elapsed(),
+            });
+        }
+        popped += 1;
+        // the estimate never decreases by more than one per move, so sequences
+        // are popped by increasing cost: the ones left after the first solution
+        // are either longer or have a bigger penalty
+        if solutions
+            .first()
+            .is_some_and(|first| sequence.cost.moves + sequence.cost.estimate > first.cost.moves)
+        {
+            break;
+        }
         let current_robot_positions = sequence
             .move_sequence
             .last()
             .map(|e| e.1)
             .unwrap_or(&robot_positions);
-        if current_robot_positions.get_robot_position(target.0) == &target.1 {
-            println!(
-                "found solution in {} moves, {} positions explored",
-                sequence.move_sequence.clone().to_vec().len(),
-                seen.len()
-            );
-            return Some(sequence);
+        if target.is_reached(current_robot_positions) {
+            solutions.push(sequence);
+            continue;
         }
-        let valid_moves: Vec<_> = (0..current_robot_positions.num_robots())
-            .flat_map(|robot| {
-                board
-                    .get_valid_moves_for_robot(robot, current_robot_positions)
-                    .iter()
-                    .map(|(direction, next_robot_position)| {
-                        (
-                            Move {
-                                robot,
-                                direction: *direction,
-                            },
-                            current_robot_positions.update(robot, next_robot_position.clone()),
-                        )
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .filter(|(_, next_robot_positions)| !seen.contains(next_robot_positions))
-            .collect();
+        // a shorter path to this position was found after this one was queued
+        if best_moves[&key(current_robot_positions)].moves < sequence.cost.moves {
+            continue;
+        }
+        let moves = sequence.cost.moves + 1;
+        let valid_moves = (0..current_robot_positions.num_robots()).flat_map(|robot| {
+            board
+                .get_valid_moves_for_robot(robot, current_robot_positions)
+                .iter()
+                .map(|(direction, next_robot_position)| {
+                    (
+                        Move {
+                            robot,
+                            direction: *direction,
+                        },
+                        current_robot_positions.update(robot, *next_robot_position),
+                    )
+                })
+                .collect::<Vec<_>>()
+        });
         for (move_, next_robot_positions) in valid_moves {
+            let next_key = key(&next_robot_positions);
+            let best = best_moves.get(&next_key);
+            if best.is_some_and(|best| {
+                best.moves < moves
+                    || (best.moves == moves && max_solutions == 1 && !smallest_penalty)
+            }) {
+                continue;
+            }
+            let reached = Reached {
+                moves,
+                penalty: sequence.cost.penalty
+                    + cost_model.penalty(
+                        &target,
+                        sequence
+                            .move_sequence
+                            .last()
+                            .map(|(previous_move, _)| previous_move),
+                        robot_bit(move_.robot) & !sequence.robots_moved != 0,
+                        &move_,
+                    ),
+                robot_positions: next_robot_positions.clone(),
+                last_move: Some(move_.clone()),
+                robots_moved: sequence.robots_moved | robot_bit(move_.robot),
+            };
+            if best.is_some_and(|best| {
+                best.moves == moves && max_solutions == 1 && best.dominates(&reached, cost_model)
+            }) {
+                continue;
+            }
+            // the target can't be reached anymore (or not soon enough)
+            let Some(estimate) =
+                estimate(&next_robot_positions).filter(|e| within_max_moves(moves, *e))
+            else {
+                continue;
+            };
             let updated_sequence_with_cost = SequenceWithCost {
                 move_sequence: sequence
                     .move_sequence
                     .append(move_.clone(), next_robot_positions.clone()),
                 cost: Cost {
-                    moves: sequence.cost.moves + 1,
-                    robot_change: sequence.cost.robot_change
-                        + match sequence.move_sequence.last() {
-                            Some((previous_move, _)) => {
-                                if move_.robot == previous_move.robot {
-                                    0
-                                } else {
-                                    1
-                                }
-                            }
-                            None => 0,
-                        },
+                    moves,
+                    estimate,
+                    penalty: reached.penalty,
                 },
+                robots_moved: reached.robots_moved,
                 phantom_position: PhantomData,
             };
-            seen.insert(next_robot_positions.clone());
+            // the cheapest sequence is kept, it rules out the most others
+            if best_moves
+                .get(&next_key)
+                .is_none_or(|best| best.moves > moves || best.penalty > reached.penalty)
+            {
+                best_moves.insert(next_key, reached);
+            }
             queue.push(updated_sequence_with_cost);
+            stats.generated_sequences += 1;
+            stats.max_queued_sequences = stats.max_queued_sequences.max(queue.len());
         }
     }
-    println!(
-        "could not find a solution, {} positions explored",
-        seen.len()
-    );
-    None
+    if solutions.is_empty() && limit.is_none() && beyond_max_moves {
+        limit = Some(SearchLimit::MaxMoves);
+    }
+    Ok(SearchOutput {
+        limit: limit.filter(|_| solutions.is_empty()),
+        solutions,
+        stats: SolveStats {
+            explored_positions: best_moves.len(),
+            duration: stopwatch.elapsed(),
+            ..stats
+        },
+    })
+}
+
+// Same as `solve`, within the limits of `options`
+pub fn solve_with_options<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
+    board: &Board<W>,
+    robot_positions: P,
+    empty_move_sequence: M,
+    target: Target,
+    options: &SolveOptions,
+) -> Result<SolveOutcome<P, M>, SolveError> {
+    let (outcome, _) =
+        solve_with_stats(board, robot_positions, empty_move_sequence, target, options)?;
+    Ok(outcome)
+}
+
+// Same as `solve_with_options`, along with statistics about the search
+pub fn solve_with_stats<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
+    board: &Board<W>,
+    robot_positions: P,
+    empty_move_sequence: M,
+    target: Target,
+    options: &SolveOptions,
+) -> Result<(SolveOutcome<P, M>, SolveStats), SolveError> {
+    let key_target = target.clone();
+    let output = search_solutions(
+        board,
+        robot_positions,
+        empty_move_sequence,
+        target,
+        SearchSettings {
+            use_heuristic: true,
+            key: |robot_positions: &P| canonical_positions(board, &key_target, robot_positions),
+            options,
+        },
+    )?;
+    let outcome = match output.limit {
+        _ if !output.solutions.is_empty() => SolveOutcome::Solved(output.solutions),
+        Some(limit) => SolveOutcome::LimitReached(limit),
+        None => SolveOutcome::NoSolution,
+    };
+    Ok((outcome, output.stats))
 }
+
+// Every shortest solution (or the first `max_solutions` of them), the ones with
+// the smallest penalty according to `cost_model` first
+pub fn solve_all<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
+    board: &Board<W>,
+    robot_positions: P,
+    empty_move_sequence: M,
+    target: Target,
+    max_solutions: Option<usize>,
+    cost_model: &CostModel,
+) -> Result<Vec<SequenceWithCost<P, M>>, SolveError> {
+    let options = SolveOptions {
+        max_solutions,
+        cost_model: *cost_model,
+        ..SolveOptions::default()
+    };
+    match solve_with_options(
+        board,
+        robot_positions,
+        empty_move_sequence,
+        target,
+        &options,
+    )? {
+        SolveOutcome::Solved(solutions) => Ok(solutions),
+        _ => Ok(vec![]),
+    }
+}
+
+// Same as `solve`, with solutions of the same length ranked by `cost_model`
+pub fn solve_with_cost_model<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
+    board: &Board<W>,
+    robot_positions: P,
+    empty_move_sequence: M,
+    target: Target,
+    cost_model: &CostModel,
+) -> Result<Option<SequenceWithCost<P, M>>, SolveError> {
+    Ok(solve_all(
+        board,
+        robot_positions,
+        empty_move_sequence,
+        target,
+        Some(1),
+        cost_model,
+    )?
+    .pop())
+}
+
+// One of the shortest solutions, along with statistics about the search
+pub fn solve<W: WallConfiguration, P: RobotPositions, M: MoveSequence<P>>(
+    board: &Board<W>,
+    robot_positions: P,
+    empty_move_sequence: M,
+    target: Target,
+) -> Result<SearchResult<P, M>, SolveError> {
+    search(board, robot_positions, empty_move_sequence, target, true)
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::solver::ascii_board::AsciiBoard;
     use crate::solver::move_sequence::MoveSequenceLinkedList;
     use crate::solver::robot_positions::RobotPositionsVec;
     use crate::solver::wall_configuration::WallConfigurationVecVec;
+    use crate::solver::{Direction, Mirror, MirrorOrientation, Position};
 
     use super::*;
+    use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
     use wasm_bindgen_test::wasm_bindgen_test;
 
     #[test]
     #[wasm_bindgen_test]
     fn test_solve_with_solution() {
-        let board = Board::new(WallConfigurationVecVec {
-            height: 6,
-            width: 5,
-            right_walls: vec![vec![], vec![2], vec![], vec![], vec![], vec![]],
-            bottom_walls: vec![vec![], vec![], vec![1], vec![], vec![]],
-        });
-        let robot_positions = RobotPositionsVec::new(vec![
-            Position::new(0, 0),
-            Position::new(1, 0),
-            Position::new(1, 2),
-            Position::new(1, 4),
-        ]);
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _________
+            |A . . . .|
+            |         |
+            |B . C|. D|
+            |    _    |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |_________|
+        "
+        .parse()
+        .unwrap();
+        let board = Board::new(wall_configuration);
         let empty_move_sequence = MoveSequenceLinkedList::<RobotPositionsVec>::empty();
         let cases: Vec<(usize, Position, usize)> = vec![
             (0, Position::new(0, 0), 0),
@@ -215,13 +850,18 @@ mod tests {
             (0, Position::new(3, 3), 7),
         ];
         for (robot, target_position, moves) in cases {
-            let solution = solve(
+            let SearchResult { solution, stats } = solve(
                 &board,
                 robot_positions.clone(),
                 empty_move_sequence.clone(),
-                (robot, target_position),
+                Target::Robot(robot, target_position),
             )
             .unwrap();
+            let solution = solution.unwrap();
+            assert_eq!(stats.depth, moves);
+            assert!(stats.explored_positions > moves);
+            assert!(stats.generated_sequences >= stats.explored_positions);
+            assert!(stats.max_queued_sequences <= stats.generated_sequences);
             dbg!(solution
                 .move_sequence
                 .clone()
@@ -240,25 +880,741 @@ mod tests {
         }
     }
 
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_solve_with_wildcard_target() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _________
+            |A . . . .|
+            |         |
+            |B . C|. D|
+            |    _    |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |_________|
+        "
+        .parse()
+        .unwrap();
+        let board = Board::new(wall_configuration);
+        let empty_move_sequence = MoveSequenceLinkedList::<RobotPositionsVec>::empty();
+        let cases: Vec<(Position, usize)> = vec![
+            (Position::new(1, 2), 0),
+            (Position::new(0, 4), 1),
+            (Position::new(5, 0), 1),
+            (Position::new(0, 3), 2),
+            (Position::new(4, 3), 5),
+            (Position::new(3, 1), 7), // one move shorter than with robot 0
+            (Position::new(2, 3), 6),
+            (Position::new(3, 3), 7),
+        ];
+        for (target_position, moves) in cases {
+            let solution = solve(
+                &board,
+                robot_positions.clone(),
+                empty_move_sequence.clone(),
+                Target::AnyRobot(target_position),
+            )
+            .unwrap()
+            .solution
+            .unwrap();
+
+            // assert correct number of moves
+            assert_eq!(solution.move_sequence.clone().to_vec().len(), moves);
+
+            // the robot making the last move should be the one standing on the target
+            if let Some((last_move, last_positions)) = solution.move_sequence.last() {
+                assert_eq!(
+                    last_positions.get_robot_position(last_move.robot),
+                    target_position
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_search_with_heuristic() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _________
+            |A . . . .|
+            |         |
+            |B . C|. D|
+            |    _    |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |_________|
+        "
+        .parse()
+        .unwrap();
+        let board = Board::new(wall_configuration);
+        let empty_move_sequence = MoveSequenceLinkedList::<RobotPositionsVec>::empty();
+        let cases = [
+            Target::Robot(0, Position::new(4, 3)),
+            Target::Robot(0, Position::new(3, 1)),
+            Target::Robot(1, Position::new(2, 3)),
+            Target::AnyRobot(Position::new(3, 3)),
+        ];
+        for target in cases {
+            let [without_heuristic, with_heuristic] = [false, true].map(|use_heuristic| {
+                search(
+                    &board,
+                    robot_positions.clone(),
+                    empty_move_sequence.clone(),
+                    target.clone(),
+                    use_heuristic,
+                )
+                .unwrap()
+            });
+            assert_eq!(
+                with_heuristic.solution.unwrap().moves().len(),
+                without_heuristic.solution.unwrap().moves().len()
+            );
+            assert!(
+                with_heuristic.stats.explored_positions
+                    < without_heuristic.stats.explored_positions,
+                "{:?}",
+                target
+            );
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_solve_all() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _________
+            |A . . . .|
+            |         |
+            |B . C|. D|
+            |    _    |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |_________|
+        "
+        .parse()
+        .unwrap();
+        let board = Board::new(wall_configuration);
+        let empty_move_sequence = MoveSequenceLinkedList::<RobotPositionsVec>::empty();
+        let target = Target::Robot(0, Position::new(1, 2));
+        let solve_all = |max_solutions, cost_model: &CostModel| {
+            solve_all(
+                &board,
+                robot_positions.clone(),
+                empty_move_sequence.clone(),
+                target.clone(),
+                max_solutions,
+                cost_model,
+            )
+            .unwrap()
+        };
+
+        let shortest = solve(
+            &board,
+            robot_positions.clone(),
+            empty_move_sequence.clone(),
+            target.clone(),
+        )
+        .unwrap()
+        .solution
+        .unwrap();
+        let solutions = solve_all(None, &CostModel::default());
+        assert!(solutions.len() > 1);
+        for (index, solution) in solutions.iter().enumerate() {
+            let moves = solution.moves();
+            assert_eq!(moves.len(), shortest.moves().len());
+            assert!(target.is_reached(&moves.last().unwrap().1));
+            // every solution is different
+            assert!(solutions[..index]
+                .iter()
+                .all(|other| other.moves() != moves));
+        }
+        assert!(solutions
+            .windows(2)
+            .all(|pair| pair[0].penalty() <= pair[1].penalty()));
+        assert_eq!(solutions[0].penalty(), shortest.penalty());
+
+        // the first solutions switch robots the least
+        let best = solve_all(Some(2), &CostModel::default());
+        assert_eq!(best.len(), 2);
+        assert_eq!(best[1].penalty(), solutions[1].penalty());
+        assert!(solve_all(Some(0), &CostModel::default()).is_empty());
+
+        // the best solution according to another cost model is one of the
+        // shortest solutions with the smallest penalty
+        let cost_model = CostModel {
+            robot_change: 0,
+            other_robot: 1,
+            bounce_back: 1,
+            ..CostModel::default()
+        };
+        let best = solve_with_cost_model(
+            &board,
+            robot_positions.clone(),
+            empty_move_sequence.clone(),
+            target.clone(),
+            &cost_model,
+        )
+        .unwrap()
+        .unwrap();
+        let penalty = |solution: &SequenceWithCost<_, _>| {
+            let moves: Vec<Move> = solution.moves().into_iter().map(|(m, _)| m).collect();
+            cost_model.total_penalty(&target, &moves)
+        };
+        assert_eq!(best.penalty(), penalty(&best));
+        assert_eq!(Some(best.penalty()), solutions.iter().map(penalty).min());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_solve_with_cost_model_on_random_puzzles() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let (height, width) = (6, 5);
+        // the default cost model doesn't always find the smallest penalty (see
+        // `search_solutions`), the same ranking with another weight does
+        let cost_models = [
+            CostModel {
+                robot_change: 2,
+                ..CostModel::default()
+            },
+            CostModel {
+                robot_change: 0,
+                new_robot: 1,
+                ..CostModel::default()
+            },
+            CostModel {
+                robot_change: 0,
+                other_robot: 1,
+                ..CostModel::default()
+            },
+            CostModel {
+                robot_change: 0,
+                bounce_back: 1,
+                ..CostModel::default()
+            },
+            CostModel {
+                robot_change: 1,
+                new_robot: 2,
+                other_robot: 1,
+                bounce_back: 1,
+            },
+        ];
+        for _ in 0..30 {
+            let board = Board::new(WallConfigurationVecVec {
+                height,
+                width,
+                right_walls: (0..height)
+                    .map(|_| (0..width - 1).filter(|_| rng.gen_bool(0.2)).collect())
+                    .collect(),
+                bottom_walls: (0..width)
+                    .map(|_| (0..height - 1).filter(|_| rng.gen_bool(0.2)).collect())
+                    .collect(),
+                blocked_squares: vec![],
+            });
+            let squares: Vec<Position> = (0..height)
+                .flat_map(|row| (0..width).map(move |col| Position::new(row, col)))
+                .collect();
+            let robot_positions =
+                RobotPositionsVec::new(squares.choose_multiple(&mut rng, 2).cloned().collect());
+            let target = Target::Robot(0, *squares.choose(&mut rng).unwrap());
+            for cost_model in &cost_models {
+                let best = solve_with_cost_model(
+                    &board,
+                    robot_positions.clone(),
+                    MoveSequenceLinkedList::empty(),
+                    target.clone(),
+                    cost_model,
+                )
+                .unwrap();
+                // the solutions are ranked by penalty
+                let solutions = solve_all(
+                    &board,
+                    robot_positions.clone(),
+                    MoveSequenceLinkedList::empty(),
+                    target.clone(),
+                    None,
+                    cost_model,
+                )
+                .unwrap();
+                assert_eq!(
+                    best.map(|best| best.penalty()),
+                    solutions.first().map(|first| first.penalty()),
+                    "{:?} {:?} {:?}",
+                    robot_positions,
+                    target,
+                    cost_model
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_search_with_canonical_positions() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _________
+            |A . . . .|
+            |         |
+            |B . C|. D|
+            |    _    |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |_________|
+        "
+        .parse()
+        .unwrap();
+        let board = Board::new(wall_configuration);
+        let empty_move_sequence = MoveSequenceLinkedList::<RobotPositionsVec>::empty();
+        let cases = [
+            Target::Robot(0, Position::new(4, 3)),
+            Target::Robot(1, Position::new(2, 3)),
+            Target::Robot(3, Position::new(5, 0)),
+            Target::AnyRobot(Position::new(3, 3)),
+        ];
+        for target in cases {
+            let canonical = search(
+                &board,
+                robot_positions.clone(),
+                empty_move_sequence.clone(),
+                target.clone(),
+                false,
+            )
+            .unwrap();
+            let ordered = search_by_key(
+                &board,
+                robot_positions.clone(),
+                empty_move_sequence.clone(),
+                target.clone(),
+                false,
+                |robot_positions| robot_positions.clone(),
+            )
+            .unwrap();
+            // swapping robots that play the same role doesn't change the
+            // solutions, so it doesn't have to be explored
+            assert_eq!(
+                canonical.solution.unwrap().moves().len(),
+                ordered.solution.unwrap().moves().len()
+            );
+            assert!(canonical.stats.explored_positions < ordered.stats.explored_positions);
+        }
+    }
+
     #[test]
     #[wasm_bindgen_test]
     fn test_solve_with_no_solution() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _____
+            |A . .|
+            |     |
+            |. B .|
+            |     |
+            |. . .|
+            |_____|
+        "
+        .parse()
+        .unwrap();
+        let board = Board::new(wall_configuration);
+        let empty_move_sequence = MoveSequenceLinkedList::<RobotPositionsVec>::empty();
+
+        assert!(solve(
+            &board,
+            robot_positions.clone(),
+            empty_move_sequence.clone(),
+            Target::Robot(0, Position::new(1, 1)), // there's no way for the robot to reach the center of the board!
+        )
+        .unwrap()
+        .solution
+        .is_none());
+
+        // nothing can stop the robot in the middle of an edge once the center
+        // is blocked
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            target,
+        } = "
+             _____
+            |A a .|
+            |     |
+            |. # .|
+            |     |
+            |. . .|
+            |_____|
+        "
+        .parse()
+        .unwrap();
+        assert!(solve(
+            &Board::new(wall_configuration),
+            robot_positions,
+            empty_move_sequence,
+            target.unwrap(),
+        )
+        .unwrap()
+        .solution
+        .is_none());
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_solve_with_options() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _________
+            |A . . . .|
+            |         |
+            |B . C|. D|
+            |    _    |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |_________|
+        "
+        .parse()
+        .unwrap();
+        let board = Board::new(wall_configuration);
+        let empty_move_sequence = MoveSequenceLinkedList::<RobotPositionsVec>::empty();
+        // the shortest solution has 5 moves
+        let target = Target::Robot(0, Position::new(1, 2));
+        let solve = |options: &SolveOptions| {
+            solve_with_options(
+                &board,
+                robot_positions.clone(),
+                empty_move_sequence.clone(),
+                target.clone(),
+                options,
+            )
+            .unwrap()
+        };
+
+        let SolveOutcome::Solved(solutions) = solve(&SolveOptions {
+            max_moves: Some(5),
+            ..SolveOptions::default()
+        }) else {
+            panic!("expected a solution");
+        };
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].moves().len(), 5);
+        assert!(matches!(
+            solve(&SolveOptions {
+                max_moves: Some(4),
+                ..SolveOptions::default()
+            }),
+            SolveOutcome::LimitReached(SearchLimit::MaxMoves)
+        ));
+        assert!(matches!(
+            solve(&SolveOptions {
+                max_explored_positions: Some(10),
+                ..SolveOptions::default()
+            }),
+            SolveOutcome::LimitReached(SearchLimit::MaxExploredPositions)
+        ));
+        assert!(matches!(
+            solve(&SolveOptions {
+                should_cancel: Some(&|| true),
+                ..SolveOptions::default()
+            }),
+            SolveOutcome::LimitReached(SearchLimit::Cancelled)
+        ));
+
+        // the first explored position is always reported
+        let reports = std::cell::RefCell::new(vec![]);
+        let observer = |progress: &Progress| reports.borrow_mut().push(*progress);
+        solve(&SolveOptions {
+            progress_observer: Some(&observer),
+            ..SolveOptions::default()
+        });
+        let reports = reports.into_inner();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].explored_positions, 1);
+        assert_eq!(reports[0].queued_sequences, 0);
+        assert!(reports[0].depth <= 5);
+
+        // limits that aren't reached don't hide impossible puzzles
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            target,
+        } = "
+             _____
+            |A a .|
+            |     |
+            |. # .|
+            |     |
+            |. . .|
+            |_____|
+        "
+        .parse()
+        .unwrap();
+        assert!(matches!(
+            solve_with_options(
+                &Board::new(wall_configuration),
+                robot_positions,
+                MoveSequenceLinkedList::empty(),
+                target.unwrap(),
+                &SolveOptions {
+                    max_moves: Some(20),
+                    max_explored_positions: Some(1000),
+                    should_cancel: Some(&|| false),
+                    ..SolveOptions::default()
+                },
+            )
+            .unwrap(),
+            SolveOutcome::NoSolution
+        ));
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_solve_with_mirrors() {
+        let AsciiBoard {
+            wall_configuration,
+            robot_positions,
+            ..
+        } = "
+             _________
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |. . . . .|
+            |         |
+            |A . . . B|
+            |_________|
+        "
+        .parse()
+        .unwrap();
+        let mirror = |row, col, orientation| Mirror {
+            position: Position::new(row, col),
+            orientation,
+            color: 1,
+        };
+        let board = Board::new(wall_configuration).with_mirrors(vec![
+            mirror(4, 2, MirrorOrientation::Slash),
+            mirror(1, 2, MirrorOrientation::Slash),
+            mirror(3, 3, MirrorOrientation::Backslash),
+        ]);
+        let empty_move_sequence = MoveSequenceLinkedList::<RobotPositionsVec>::empty();
+
+        let cases = vec![
+            // bounces off two mirrors (3 moves without the mirrors)
+            (Target::Robot(0, Position::new(1, 4)), 1),
+            // goes through the mirror of its own color
+            (Target::Robot(1, Position::new(4, 1)), 1),
+        ];
+        for (target, expected_moves) in cases {
+            let solution = solve(
+                &board,
+                robot_positions.clone(),
+                empty_move_sequence.clone(),
+                target.clone(),
+            )
+            .unwrap()
+            .solution
+            .unwrap();
+            assert_eq!(solution.moves().len(), expected_moves, "{:?}", target);
+        }
+
+        assert_eq!(
+            board.get_valid_paths_for_robot(0, &robot_positions)[1],
+            (
+                Direction::Right,
+                vec![
+                    Position::new(4, 2),
+                    Position::new(1, 2),
+                    Position::new(1, 4)
+                ]
+            )
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_validate_robot() {
         let board = Board::new(WallConfigurationVecVec {
             height: 3,
             width: 3,
             right_walls: vec![vec![], vec![], vec![]],
             bottom_walls: vec![vec![], vec![], vec![]],
+            blocked_squares: vec![Position::new(1, 2)],
         });
         let robot_positions =
             RobotPositionsVec::new(vec![Position::new(0, 0), Position::new(1, 1)]);
+        assert_eq!(validate_robot(&board, &robot_positions, 1), Ok(()));
+        assert_eq!(
+            validate_robot(&board, &robot_positions, 2),
+            Err(SolveError::RobotOutOfRange {
+                robot: 2,
+                num_robots: 2
+            })
+        );
+        assert_eq!(
+            validate_robot(
+                &board,
+                &RobotPositionsVec::new(vec![Position::new(0, 0), Position::new(1, 2)]),
+                0
+            ),
+            Err(SolveError::RobotOnBlockedSquare { robot: 1 })
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_solve_with_invalid_input() {
+        let board = Board::new(WallConfigurationVecVec {
+            height: 3,
+            width: 3,
+            right_walls: vec![vec![], vec![], vec![]],
+            bottom_walls: vec![vec![], vec![], vec![]],
+            blocked_squares: vec![Position::new(1, 2)],
+        });
         let empty_move_sequence = MoveSequenceLinkedList::<RobotPositionsVec>::empty();
+        let cases: Vec<(Vec<Position>, Target, SolveError)> = vec![
+            (
+                vec![Position::new(0, 0), Position::new(3, 1)],
+                Target::Robot(0, Position::new(2, 2)),
+                SolveError::RobotOutOfBounds { robot: 1 },
+            ),
+            (
+                vec![
+                    Position::new(0, 0),
+                    Position::new(1, 1),
+                    Position::new(0, 0),
+                ],
+                Target::Robot(0, Position::new(2, 2)),
+                SolveError::OverlappingRobots {
+                    robot: 0,
+                    other_robot: 2,
+                },
+            ),
+            (
+                vec![Position::new(0, 0), Position::new(1, 2)],
+                Target::Robot(0, Position::new(2, 2)),
+                SolveError::RobotOnBlockedSquare { robot: 1 },
+            ),
+            (
+                vec![Position::new(0, 0)],
+                Target::AnyRobot(Position::new(0, 3)),
+                SolveError::TargetOutOfBounds,
+            ),
+            (
+                vec![Position::new(0, 0)],
+                Target::Robot(0, Position::new(1, 2)),
+                SolveError::TargetOnBlockedSquare,
+            ),
+            (
+                vec![Position::new(0, 0), Position::new(1, 1)],
+                Target::Robot(2, Position::new(2, 2)),
+                SolveError::TargetRobotOutOfRange {
+                    robot: 2,
+                    num_robots: 2,
+                },
+            ),
+            (
+                vec![],
+                Target::AnyRobot(Position::new(2, 2)),
+                SolveError::MissingTargetRobot,
+            ),
+        ];
+        for (positions, target, error) in cases {
+            assert_eq!(
+                solve(
+                    &board,
+                    RobotPositionsVec::new(positions),
+                    empty_move_sequence.clone(),
+                    target,
+                )
+                .err(),
+                Some(error)
+            );
+        }
 
-        assert!(solve(
-            &board,
-            robot_positions.clone(),
-            empty_move_sequence.clone(),
-            (0, Position::new(1, 1)), // there's no way for the robot to reach the center of the board!
-        )
-        .is_none());
+        let invalid_board = Board::new(WallConfigurationVecVec {
+            height: 3,
+            width: 3,
+            right_walls: vec![vec![3], vec![], vec![]],
+            bottom_walls: vec![vec![], vec![], vec![]],
+            blocked_squares: vec![],
+        });
+        assert_eq!(
+            solve(
+                &Board::new(WallConfigurationVecVec {
+                    height: 3,
+                    width: 3,
+                    right_walls: vec![vec![], vec![], vec![]],
+                    bottom_walls: vec![vec![], vec![], vec![]],
+                    blocked_squares: vec![],
+                })
+                .with_mirrors(vec![Mirror {
+                    position: Position::new(3, 0),
+                    orientation: MirrorOrientation::Slash,
+                    color: 0,
+                }]),
+                RobotPositionsVec::new(vec![Position::new(0, 0)]),
+                empty_move_sequence.clone(),
+                Target::Robot(0, Position::new(2, 2)),
+            )
+            .err(),
+            Some(SolveError::InvalidMirror {
+                position: Position::new(3, 0)
+            })
+        );
+        assert_eq!(
+            solve(
+                &invalid_board,
+                RobotPositionsVec::new(vec![Position::new(0, 0)]),
+                empty_move_sequence.clone(),
+                Target::Robot(0, Position::new(2, 2)),
+            )
+            .err(),
+            Some(SolveError::InvalidWallConfiguration)
+        );
     }
 }
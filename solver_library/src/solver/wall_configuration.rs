@@ -7,6 +7,10 @@ pub trait WallConfiguration {
     fn get_height(&self) -> usize;
     fn get_width(&self) -> usize;
 
+    // Blocked squares (like the central block of the real boards) can't be
+    // entered by robots, they act as if they were surrounded by walls
+    fn is_blocked(&self, position: &Position) -> bool;
+
     // How far can a robot travel until hitting a wall?
     fn next_wall_up(&self, position: &Position) -> Option<usize>;
     fn next_wall_down(&self, position: &Position) -> Option<usize>;
@@ -15,23 +19,29 @@ pub trait WallConfiguration {
 }
 
 // Immutable container storing the layout of the walls in a Vec<Vec<usize>>
+#[derive(Clone, Debug, PartialEq)]
 pub struct WallConfigurationVecVec {
     // TODO: make these private
     pub height: usize,
     pub width: usize,
     pub right_walls: Vec<Vec<usize>>,
     pub bottom_walls: Vec<Vec<usize>>,
+    pub blocked_squares: Vec<Position>,
 }
 
 impl WallConfigurationVecVec {
-    fn next_wall(walls: &[usize], position: usize, diff: isize) -> Option<usize> {
-        let candidate_walls = walls.iter().filter(|pos| match diff {
-            1 => **pos >= position,
-            -1 => **pos < position,
+    fn next_wall(
+        walls: impl Iterator<Item = usize>,
+        position: usize,
+        diff: isize,
+    ) -> Option<usize> {
+        let candidate_walls = walls.filter(|pos| match diff {
+            1 => *pos >= position,
+            -1 => *pos < position,
             _ => unreachable!(),
         });
         match diff {
-            1 => candidate_walls.min().copied(),
+            1 => candidate_walls.min(),
             -1 => candidate_walls.max().map(|pos| pos + 1),
             _ => unreachable!(),
         }
@@ -47,6 +57,10 @@ impl WallConfiguration for WallConfigurationVecVec {
         self.width
     }
 
+    fn is_blocked(&self, position: &Position) -> bool {
+        self.blocked_squares.contains(position)
+    }
+
     fn is_valid(&self) -> bool {
         self.right_walls.len() == self.height
             && self.bottom_walls.len() == self.width
@@ -60,44 +74,181 @@ impl WallConfiguration for WallConfigurationVecVec {
                 .iter()
                 .flatten()
                 .all(|row| *row < self.height)
+            && self
+                .blocked_squares
+                .iter()
+                .all(|position| position.row < self.height && position.col < self.width)
     }
 
+    // A blocked square stops a robot like a wall on the side of the square the
+    // robot comes from
     fn next_wall_up(&self, position: &Position) -> Option<usize> {
+        let blocked_rows = self
+            .blocked_squares
+            .iter()
+            .filter(|square| square.col == position.col)
+            .map(|square| square.row);
         WallConfigurationVecVec::next_wall(
-            self.bottom_walls.get(position.col).unwrap(),
+            self.bottom_walls
+                .get(position.col)
+                .unwrap()
+                .iter()
+                .copied()
+                .chain(blocked_rows),
             position.row,
             -1,
         )
     }
 
     fn next_wall_down(&self, position: &Position) -> Option<usize> {
+        let blocked_rows = self
+            .blocked_squares
+            .iter()
+            .filter(|square| square.col == position.col)
+            .filter_map(|square| square.row.checked_sub(1));
         WallConfigurationVecVec::next_wall(
-            self.bottom_walls.get(position.col).unwrap(),
+            self.bottom_walls
+                .get(position.col)
+                .unwrap()
+                .iter()
+                .copied()
+                .chain(blocked_rows),
             position.row,
             1,
         )
     }
 
     fn next_wall_right(&self, position: &Position) -> Option<usize> {
+        let blocked_cols = self
+            .blocked_squares
+            .iter()
+            .filter(|square| square.row == position.row)
+            .filter_map(|square| square.col.checked_sub(1));
         WallConfigurationVecVec::next_wall(
-            self.right_walls.get(position.row).unwrap(),
+            self.right_walls
+                .get(position.row)
+                .unwrap()
+                .iter()
+                .copied()
+                .chain(blocked_cols),
             position.col,
             1,
         )
     }
 
     fn next_wall_left(&self, position: &Position) -> Option<usize> {
+        let blocked_cols = self
+            .blocked_squares
+            .iter()
+            .filter(|square| square.row == position.row)
+            .map(|square| square.col);
         WallConfigurationVecVec::next_wall(
-            self.right_walls.get(position.row).unwrap(),
+            self.right_walls
+                .get(position.row)
+                .unwrap()
+                .iter()
+                .copied()
+                .chain(blocked_cols),
             position.col,
             -1,
         )
     }
 }
 
+// Marks a direction without any wall in a `WallConfigurationTable`
+const NO_WALL: u16 = u16::MAX;
+
+// Immutable container storing, for every square, how far a robot can travel in
+// each direction until hitting a wall, so that moves don't have to scan the
+// walls of the row or column
+#[derive(Clone, Debug, PartialEq)]
+pub struct WallConfigurationTable {
+    height: usize,
+    width: usize,
+    blocked: Vec<bool>,
+    // up, down, right and left stops of each square, row by row
+    stops: Vec<[u16; 4]>,
+}
+
+impl WallConfigurationTable {
+    // Returns `None` if the wall configuration is invalid or too large
+    pub fn new<W: WallConfiguration>(wall_configuration: &W) -> Option<Self> {
+        let height = wall_configuration.get_height();
+        let width = wall_configuration.get_width();
+        if !wall_configuration.is_valid() || height >= NO_WALL as usize || width >= NO_WALL as usize
+        {
+            return None;
+        }
+        let pack = |stop: Option<usize>| stop.map_or(NO_WALL, |stop| stop as u16);
+        let positions =
+            (0..height).flat_map(|row| (0..width).map(move |col| Position::new(row, col)));
+        Some(WallConfigurationTable {
+            height,
+            width,
+            blocked: positions
+                .clone()
+                .map(|position| wall_configuration.is_blocked(&position))
+                .collect(),
+            stops: positions
+                .map(|position| {
+                    [
+                        pack(wall_configuration.next_wall_up(&position)),
+                        pack(wall_configuration.next_wall_down(&position)),
+                        pack(wall_configuration.next_wall_right(&position)),
+                        pack(wall_configuration.next_wall_left(&position)),
+                    ]
+                })
+                .collect(),
+        })
+    }
+
+    fn stop(&self, position: &Position, direction: usize) -> Option<usize> {
+        let stop = self.stops[position.row * self.width + position.col][direction];
+        (stop != NO_WALL).then_some(stop as usize)
+    }
+}
+
+impl WallConfiguration for WallConfigurationTable {
+    fn get_height(&self) -> usize {
+        self.height
+    }
+
+    fn get_width(&self) -> usize {
+        self.width
+    }
+
+    fn is_blocked(&self, position: &Position) -> bool {
+        position.row < self.height
+            && position.col < self.width
+            && self.blocked[position.row * self.width + position.col]
+    }
+
+    // Tables are only built from valid wall configurations
+    fn is_valid(&self) -> bool {
+        true
+    }
+
+    fn next_wall_up(&self, position: &Position) -> Option<usize> {
+        self.stop(position, 0)
+    }
+
+    fn next_wall_down(&self, position: &Position) -> Option<usize> {
+        self.stop(position, 1)
+    }
+
+    fn next_wall_right(&self, position: &Position) -> Option<usize> {
+        self.stop(position, 2)
+    }
+
+    fn next_wall_left(&self, position: &Position) -> Option<usize> {
+        self.stop(position, 3)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solver::boards::BoardLayout;
     use wasm_bindgen_test::wasm_bindgen_test;
 
     #[test]
@@ -108,6 +259,7 @@ mod tests {
             width: 5,
             right_walls: vec![vec![1, 2], vec![], vec![], vec![], vec![], vec![]],
             bottom_walls: vec![vec![1, 2], vec![], vec![], vec![], vec![]],
+            blocked_squares: vec![Position::new(4, 4)],
         };
         assert!(wall_configuration.is_valid());
         type Case = (
@@ -124,6 +276,9 @@ mod tests {
             (Position::new(3, 0), Some(3), None, None, None),
             (Position::new(0, 2), None, None, Some(2), Some(2)), // horizontally stuck between two walls
             (Position::new(2, 0), Some(2), Some(2), None, None), // vertically stuck between two walls
+            (Position::new(1, 4), None, Some(3), None, None),    // above a blocked square
+            (Position::new(5, 4), Some(5), None, None, None),    // below a blocked square
+            (Position::new(4, 0), Some(3), None, Some(3), None), // left of a blocked square
         ];
 
         for (position, up, down, right, left) in cases {
@@ -133,4 +288,51 @@ mod tests {
             assert_eq!(wall_configuration.next_wall_left(&position), left);
         }
     }
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_wall_configuration_table() {
+        let invalid = WallConfigurationVecVec {
+            height: 1,
+            width: 1,
+            right_walls: vec![],
+            bottom_walls: vec![vec![]],
+            blocked_squares: vec![],
+        };
+        assert!(WallConfigurationTable::new(&invalid).is_none());
+
+        // the table has to agree with the walls it was built from everywhere
+        // on the board
+        for layout in BoardLayout::all() {
+            let wall_configuration = layout.wall_configuration();
+            let table = WallConfigurationTable::new(&wall_configuration).unwrap();
+            assert!(table.is_valid());
+            assert_eq!(table.get_height(), wall_configuration.get_height());
+            assert_eq!(table.get_width(), wall_configuration.get_width());
+            for row in 0..table.get_height() {
+                for col in 0..table.get_width() {
+                    let position = Position::new(row, col);
+                    assert_eq!(
+                        table.is_blocked(&position),
+                        wall_configuration.is_blocked(&position)
+                    );
+                    assert_eq!(
+                        table.next_wall_up(&position),
+                        wall_configuration.next_wall_up(&position)
+                    );
+                    assert_eq!(
+                        table.next_wall_down(&position),
+                        wall_configuration.next_wall_down(&position)
+                    );
+                    assert_eq!(
+                        table.next_wall_right(&position),
+                        wall_configuration.next_wall_right(&position)
+                    );
+                    assert_eq!(
+                        table.next_wall_left(&position),
+                        wall_configuration.next_wall_left(&position)
+                    );
+                }
+            }
+        }
+    }
 }
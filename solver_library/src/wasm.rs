@@ -1,13 +1,20 @@
 use wasm_bindgen::prelude::*;
 
+use rand::{rngs::SmallRng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
 use crate::solver::board::Board;
+use crate::solver::boards::BoardLayout;
+use crate::solver::cost_model::CostModel;
+use crate::solver::error::SolveError;
+use crate::solver::generator::{self, GenerateOptions};
 use crate::solver::move_sequence::{MoveSequence, MoveSequenceLinkedList};
-use crate::solver::robot_positions::{RobotPositions, RobotPositionsVec};
-use crate::solver::solver;
-use crate::solver::wall_configuration::WallConfigurationVecVec;
-use crate::solver::Direction;
+use crate::solver::progress::Progress;
+use crate::solver::puzzle::Puzzle;
+use crate::solver::robot_positions::{RobotPositions, RobotPositionsPacked, RobotPositionsVec};
+use crate::solver::solver::{self, SearchLimit, SolveOutcome};
+use crate::solver::wall_configuration::{WallConfigurationTable, WallConfigurationVecVec};
+use crate::solver::{Direction, Mirror, Target, TargetChip};
 
 #[wasm_bindgen]
 extern "C" {
@@ -43,6 +50,7 @@ impl Position {
     }
 
     // We can't generate WASM bindings for functions of the Display trait!
+    #[allow(clippy::inherent_to_string)]
     #[wasm_bindgen]
     pub fn to_string(&self) -> String {
         format!("{:?}", self)
@@ -55,6 +63,93 @@ pub struct Move {
     pub robot: usize,
     pub direction: usize,
     pub robot_positions: Vec<Position>,
+    // squares where the robot bounced off a mirror, then the square where it
+    // stopped
+    pub path: Vec<Position>,
+}
+
+// Options of `solve_puzzle_all`, see `solver::solver::SolveOptions`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct SolveOptions {
+    max_moves: Option<usize>,
+    max_explored_positions: Option<usize>,
+    // `null` for every solution, which can be exponentially many
+    max_solutions: Option<usize>,
+    cost_model: CostModel,
+}
+
+impl Default for SolveOptions {
+    // Same as the native options: a single solution, without limits
+    fn default() -> Self {
+        let options = solver::SolveOptions::default();
+        SolveOptions {
+            max_moves: options.max_moves,
+            max_explored_positions: options.max_explored_positions,
+            max_solutions: options.max_solutions,
+            cost_model: options.cost_model,
+        }
+    }
+}
+
+// See `solver::solver::SolveStats`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SolveStats {
+    explored_positions: usize,
+    generated_sequences: usize,
+    max_queued_sequences: usize,
+    depth: usize,
+    duration_milliseconds: f64,
+}
+
+impl From<solver::SolveStats> for SolveStats {
+    fn from(stats: solver::SolveStats) -> Self {
+        SolveStats {
+            explored_positions: stats.explored_positions,
+            generated_sequences: stats.generated_sequences,
+            max_queued_sequences: stats.max_queued_sequences,
+            depth: stats.depth,
+            duration_milliseconds: stats.duration.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+// Output of `solve` and `solve_puzzle`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Solution {
+    // empty if there is no solution
+    solution: Vec<Move>,
+    stats: SolveStats,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Solutions {
+    solutions: Vec<Vec<Move>>,
+    // `null` unless the search gave up without finding a solution
+    limit: Option<SearchLimit>,
+    stats: SolveStats,
+}
+
+impl From<Solutions> for Solution {
+    fn from(solutions: Solutions) -> Self {
+        Solution {
+            solution: solutions.solutions.into_iter().next().unwrap_or_default(),
+            stats: solutions.stats,
+        }
+    }
+}
+
+// Argument of the `onProgress` callback of `solve_puzzle_all`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProgressEvent {
+    depth: usize,
+    explored_positions: usize,
+    queued_sequences: usize,
+    elapsed_milliseconds: f64,
 }
 
 #[derive(Deserialize)]
@@ -62,8 +157,51 @@ pub struct Move {
 struct WallConfiguration {
     right_walls: Vec<Vec<usize>>,
     bottom_walls: Vec<Vec<usize>>,
+    #[serde(default)]
+    blocked_squares: Vec<crate::solver::Position>,
+    #[serde(default)]
+    mirrors: Vec<Mirror>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TargetSquare {
+    position: Position,
+    // `None` (`null` in JS) for the vortex, which can be reached by any robot
+    robot: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RandomBoard {
+    height: usize,
+    width: usize,
+    right_walls: Vec<Vec<usize>>,
+    bottom_walls: Vec<Vec<usize>>,
+    blocked_squares: Vec<Position>,
+    targets: Vec<TargetSquare>,
+}
+
+// Errors are thrown as plain objects so that the web worker can tell them apart
+// using their `code`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Error {
+    code: &'static str,
+    message: String,
+}
+
+impl From<SolveError> for JsValue {
+    fn from(error: SolveError) -> Self {
+        serde_wasm_bindgen::to_value(&Error {
+            code: error.code(),
+            message: error.to_string(),
+        })
+        .unwrap_or_else(|error| error.into())
+    }
 }
 
+// Shortest solution of the puzzle, along with statistics about the search
 #[wasm_bindgen]
 pub fn solve(
     robot_positions: Vec<Position>,
@@ -72,55 +210,351 @@ pub fn solve(
     wall_configuration: JsValue,
     target: Position,
     target_robot: Option<usize>,
-) -> JsValue {
+) -> Result<JsValue, JsValue> {
     let wall_configuration: WallConfiguration = serde_wasm_bindgen::from_value(wall_configuration)
-        .expect("Deserializing wall configuration");
+        .map_err(|_| SolveError::InvalidWallConfiguration)?;
+    let mirrors = wall_configuration.mirrors;
     let wall_configuration = WallConfigurationVecVec {
         right_walls: wall_configuration.right_walls,
         bottom_walls: wall_configuration.bottom_walls,
+        blocked_squares: wall_configuration.blocked_squares,
         height,
         width,
     };
-    assert!(crate::solver::wall_configuration::WallConfiguration::is_valid(&wall_configuration));
 
-    let board = Board::new(wall_configuration);
+    let board = Board::new(wall_configuration).with_mirrors(mirrors);
     let robot_positions = RobotPositionsVec::new(
         robot_positions
             .into_iter()
             .map(|p| crate::solver::Position::new(p.row, p.col))
             .collect(),
     );
-    let solution = solver::solve(
-        &board,
+    let target_position = crate::solver::Position::new(target.row, target.col);
+    let target = match target_robot {
+        Some(robot) => Target::Robot(robot, target_position),
+        None => Target::AnyRobot(target_position),
+    };
+    let solutions = solve_compact(
+        board,
         robot_positions,
-        MoveSequenceLinkedList::empty(),
-        (
-            target_robot.unwrap(),
-            crate::solver::Position::new(target.row, target.col),
-        ),
-    );
-    let output = match solution {
-        Some(sequence) => sequence
-            .moves()
+        target,
+        &solver::SolveOptions::default(),
+    )?;
+    Ok(serde_wasm_bindgen::to_value(&Solution::from(solutions))?)
+}
+
+// Standard board made of the four quadrant tiles, picked at random from the
+// given seed
+#[wasm_bindgen]
+pub fn random_board(seed: u32) -> Result<JsValue, JsValue> {
+    let layout = BoardLayout::random(&mut SmallRng::seed_from_u64(seed.into()));
+    let wall_configuration = layout.wall_configuration();
+    let output = RandomBoard {
+        height: wall_configuration.height,
+        width: wall_configuration.width,
+        right_walls: wall_configuration.right_walls,
+        bottom_walls: wall_configuration.bottom_walls,
+        blocked_squares: wall_configuration
+            .blocked_squares
             .iter()
-            .map(|(move_, robot_positions)| Move {
-                robot: move_.robot,
-                direction: (match move_.direction {
-                    Direction::Up => 0,
-                    Direction::Left => 1,
-                    Direction::Down => 2,
-                    Direction::Right => 3,
-                }),
-                robot_positions: (0..robot_positions.num_robots())
-                    .map(|i| robot_positions.get_robot_position(i).to_owned())
-                    .map(|p| Position {
-                        row: p.row,
-                        col: p.col,
-                    })
-                    .collect(),
+            .map(|square| Position::new(square.row, square.col))
+            .collect(),
+        targets: layout
+            .target_squares()
+            .into_iter()
+            .map(|target| TargetSquare {
+                position: Position {
+                    row: target.position.row,
+                    col: target.position.col,
+                },
+                robot: match target.chip {
+                    TargetChip::Colored(robot, _) => Some(robot),
+                    TargetChip::Vortex => None,
+                },
             })
             .collect(),
-        None => vec![],
     };
-    serde_wasm_bindgen::to_value(&output).unwrap()
+    Ok(serde_wasm_bindgen::to_value(&output)?)
+}
+
+// Random puzzle with the walls and the number of robots of the given one
+// (format of `solver::puzzle::Puzzle`), whose shortest solutions have between
+// `min_moves` and `max_moves` moves, drawn from the given seed. Returns
+// `undefined` if no such puzzle was found.
+#[wasm_bindgen]
+pub fn generate_puzzle(
+    puzzle: JsValue,
+    seed: u32,
+    min_moves: usize,
+    max_moves: usize,
+) -> Result<JsValue, JsValue> {
+    let puzzle: Puzzle =
+        serde_wasm_bindgen::from_value(puzzle).map_err(|error| SolveError::MalformedPuzzle {
+            message: error.to_string(),
+        })?;
+    let (board, robot_positions, _) = puzzle.into_parts()?;
+    let options = GenerateOptions {
+        num_robots: robot_positions.num_robots(),
+        ..GenerateOptions::new(min_moves, max_moves)
+    };
+    let generated =
+        generator::generate(&board, &mut SmallRng::seed_from_u64(seed.into()), &options)?;
+    let output = generated.map(|generated| {
+        let mut puzzle = Puzzle::new(
+            board.get_wall_configuration().clone(),
+            &generated.robot_positions,
+            generated.target,
+        );
+        puzzle.mirrors = board.get_mirrors().to_vec();
+        puzzle
+    });
+    Ok(serde_wasm_bindgen::to_value(&output)?)
+}
+
+// Fewest moves for the robot to reach each square of the board of the puzzle
+// (format of `solver::puzzle::Puzzle`), the other robots staying where they
+// are: one array per row, with `undefined` for the squares it can't reach (see
+// `Board::distance_map`)
+#[wasm_bindgen]
+pub fn distance_map(puzzle: JsValue, robot: usize) -> Result<JsValue, JsValue> {
+    let (board, robot_positions) = distance_map_input(puzzle, robot)?;
+    let distances = board.distance_map(robot, &robot_positions);
+    Ok(serde_wasm_bindgen::to_value(&distance_rows(
+        &board, distances,
+    ))?)
+}
+
+// Same as `distance_map`, but the other robots can move too, up to `max_moves`
+// moves in total (see `Board::distance_map_with_moving_robots`)
+#[wasm_bindgen]
+pub fn distance_map_with_moving_robots(
+    puzzle: JsValue,
+    robot: usize,
+    max_moves: usize,
+) -> Result<JsValue, JsValue> {
+    let (board, robot_positions) = distance_map_input(puzzle, robot)?;
+    let distances = board.distance_map_with_moving_robots(robot, &robot_positions, max_moves);
+    Ok(serde_wasm_bindgen::to_value(&distance_rows(
+        &board, distances,
+    ))?)
+}
+
+fn distance_map_input(
+    puzzle: JsValue,
+    robot: usize,
+) -> Result<(Board<WallConfigurationVecVec>, RobotPositionsVec), SolveError> {
+    let puzzle: Puzzle =
+        serde_wasm_bindgen::from_value(puzzle).map_err(|error| SolveError::MalformedPuzzle {
+            message: error.to_string(),
+        })?;
+    let (board, robot_positions, _) = puzzle.into_parts()?;
+    solver::validate_robot(&board, &robot_positions, robot)?;
+    Ok((board, robot_positions))
+}
+
+fn distance_rows(
+    board: &Board<WallConfigurationVecVec>,
+    distances: Vec<Option<usize>>,
+) -> Vec<Vec<Option<usize>>> {
+    distances
+        .chunks(board.get_width())
+        .map(|row| row.to_vec())
+        .collect()
+}
+
+// Same as `solve`, but for a puzzle in the format of `solver::puzzle::Puzzle`
+#[wasm_bindgen]
+pub fn solve_puzzle(puzzle: JsValue) -> Result<JsValue, JsValue> {
+    let puzzle: Puzzle =
+        serde_wasm_bindgen::from_value(puzzle).map_err(|error| SolveError::MalformedPuzzle {
+            message: error.to_string(),
+        })?;
+    let (board, robot_positions, target) = puzzle.into_parts()?;
+    let solutions = solve_compact(
+        board,
+        robot_positions,
+        target,
+        &solver::SolveOptions::default(),
+    )?;
+    Ok(serde_wasm_bindgen::to_value(&Solution::from(solutions))?)
+}
+
+// Shortest solutions of the puzzle (a single one unless `maxSolutions` is
+// given, and within the limits of the options), ranked by the cost model (see
+// `solver::cost_model::CostModel`). `on_progress` is called every few thousand
+// explored positions.
+#[wasm_bindgen]
+pub fn solve_puzzle_all(
+    puzzle: JsValue,
+    options: JsValue,
+    on_progress: Option<js_sys::Function>,
+) -> Result<JsValue, JsValue> {
+    let puzzle: Puzzle =
+        serde_wasm_bindgen::from_value(puzzle).map_err(|error| SolveError::MalformedPuzzle {
+            message: error.to_string(),
+        })?;
+    let options: SolveOptions = if options.is_undefined() || options.is_null() {
+        SolveOptions::default()
+    } else {
+        serde_wasm_bindgen::from_value(options).map_err(|error| {
+            SolveError::MalformedSolveOptions {
+                message: error.to_string(),
+            }
+        })?
+    };
+    let (board, robot_positions, target) = puzzle.into_parts()?;
+    let observer = |progress: &Progress| {
+        let Some(on_progress) = &on_progress else {
+            return;
+        };
+        let event = ProgressEvent {
+            depth: progress.depth,
+            explored_positions: progress.explored_positions,
+            queued_sequences: progress.queued_sequences,
+            elapsed_milliseconds: progress.elapsed.as_secs_f64() * 1000.0,
+        };
+        if let Ok(event) = serde_wasm_bindgen::to_value(&event) {
+            // the search carries on if the callback throws
+            let _ = on_progress.call1(&JsValue::NULL, &event);
+        }
+    };
+    let solutions = solve_compact(
+        board,
+        robot_positions,
+        target,
+        &solver::SolveOptions {
+            max_moves: options.max_moves,
+            max_explored_positions: options.max_explored_positions,
+            max_solutions: options.max_solutions,
+            cost_model: options.cost_model,
+            progress_observer: on_progress.is_some().then_some(&observer as _),
+            ..solver::SolveOptions::default()
+        },
+    )?;
+    Ok(serde_wasm_bindgen::to_value(&solutions)?)
+}
+
+// Searches with the compact containers, which are much cheaper to copy and hash
+// than the ones built from the input
+fn solve_compact(
+    board: Board<WallConfigurationVecVec>,
+    robot_positions: RobotPositionsVec,
+    target: Target,
+    options: &solver::SolveOptions,
+) -> Result<Solutions, SolveError> {
+    let wall_configuration = WallConfigurationTable::new(board.get_wall_configuration())
+        .ok_or(SolveError::InvalidWallConfiguration)?;
+    let board = board.with_wall_configuration(wall_configuration);
+    let positions: Vec<_> = (0..robot_positions.num_robots())
+        .map(|robot| robot_positions.get_robot_position(robot))
+        .collect();
+    match RobotPositionsPacked::new(&positions, board.get_height(), board.get_width()) {
+        Some(robot_positions) => solve_board(&board, robot_positions, target, options),
+        // too many robots, boards larger than 16x16, or robots out of the board
+        // (which the solver reports)
+        None => solve_board(&board, robot_positions, target, options),
+    }
+}
+
+fn solve_board<W: crate::solver::wall_configuration::WallConfiguration, P: RobotPositions>(
+    board: &Board<W>,
+    robot_positions: P,
+    target: Target,
+    options: &solver::SolveOptions,
+) -> Result<Solutions, SolveError> {
+    let (outcome, stats) = solver::solve_with_stats(
+        board,
+        robot_positions.clone(),
+        MoveSequenceLinkedList::empty(),
+        target,
+        options,
+    )?;
+    let (solutions, limit) = match outcome {
+        SolveOutcome::Solved(solutions) => (solutions, None),
+        SolveOutcome::NoSolution => (vec![], None),
+        SolveOutcome::LimitReached(limit) => (vec![], Some(limit)),
+    };
+    let to_dto = |p: &crate::solver::Position| Position {
+        row: p.row,
+        col: p.col,
+    };
+    let solutions = solutions
+        .into_iter()
+        .map(|sequence| {
+            let mut previous_robot_positions = robot_positions.clone();
+            sequence
+                .moves()
+                .into_iter()
+                .map(|(move_, robot_positions)| {
+                    let path = board
+                        .get_valid_paths_for_robot(move_.robot, &previous_robot_positions)
+                        .into_iter()
+                        .find(|(direction, _)| *direction == move_.direction)
+                        .map(|(_, path)| path.iter().map(to_dto).collect())
+                        .unwrap_or_default();
+                    previous_robot_positions = robot_positions.clone();
+                    Move {
+                        robot: move_.robot,
+                        direction: (match move_.direction {
+                            Direction::Up => 0,
+                            Direction::Left => 1,
+                            Direction::Down => 2,
+                            Direction::Right => 3,
+                        }),
+                        robot_positions: (0..robot_positions.num_robots())
+                            .map(|i| to_dto(&robot_positions.get_robot_position(i)))
+                            .collect(),
+                        path,
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    Ok(Solutions {
+        solutions,
+        limit,
+        stats: stats.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::Position;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_default_solve_options() {
+        // leaving out `maxSolutions` mustn't enumerate every solution
+        let options = SolveOptions::default();
+        assert_eq!(options.max_solutions, Some(1));
+        assert_eq!(
+            options.max_solutions,
+            solver::SolveOptions::default().max_solutions
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test]
+    fn test_solve_compact_on_large_board() {
+        // too large for `RobotPositionsPacked`, even though the robot starts
+        // in the squares it can hold
+        let board = Board::new(WallConfigurationVecVec {
+            height: 20,
+            width: 20,
+            right_walls: vec![vec![]; 20],
+            bottom_walls: vec![vec![]; 20],
+            blocked_squares: vec![],
+        });
+        let solutions = solve_compact(
+            board,
+            RobotPositionsVec::new(vec![Position::new(0, 0)]),
+            Target::Robot(0, Position::new(19, 19)),
+            &solver::SolveOptions::default(),
+        )
+        .unwrap();
+        assert!(!solutions.solutions.is_empty());
+        assert!(solutions.solutions.iter().all(|moves| moves.len() == 2));
+    }
 }
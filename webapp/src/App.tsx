@@ -1,10 +1,60 @@
-// import { useState } from "react";
+import { useState } from "react";
 import "./App.css";
 
 // import { useFibonnacci } from "./worker/useFibonacci";
 import { Board } from "./board/Board";
 
+// Board made of the A sides of the four quadrant tiles, see
+// `solver_library/src/solver/boards.rs`
+const defaultWallConfiguration = {
+  rightWalls: [
+    [4],
+    [9],
+    [6],
+    [],
+    [2, 8],
+    [12],
+    [4, 13],
+    [6, 8],
+    [6, 8, 9],
+    [4],
+    [13],
+    [],
+    [6, 12],
+    [5, 9],
+    [2],
+    [5, 10],
+  ],
+  bottomWalls: [
+    [2, 9],
+    [],
+    [3],
+    [4, 14],
+    [8],
+    [5],
+    [2, 12],
+    [6, 8],
+    [4, 6, 8],
+    [13],
+    [0, 7],
+    [],
+    [11],
+    [5, 12],
+    [9],
+    [2, 8],
+  ],
+  blockedSquares: [
+    { row: 7, col: 7 },
+    { row: 7, col: 8 },
+    { row: 8, col: 7 },
+    { row: 8, col: 8 },
+  ],
+};
+
 function App() {
+  const [wallConfiguration, setWallConfiguration] = useState(
+    defaultWallConfiguration
+  );
   // const [input, setInput] = useState("42");
   // const fibonacciResult = useFibonnacci(Number(input));
   return (
@@ -29,49 +79,22 @@ function App() {
 
       <h1>Ricochet Robots ❤️</h1>
       <p>You can drag and drop the robots and target to other squares to solve other positions.</p>
-      <p style={{ fontStyle: "italic" }}>(It is not possible to change the layout of the walls, maybe in a future version!)</p>
-      <Board
-        height={16}
-        width={16}
-        wallConfiguration={{
-          rightWalls: [
-            [4],
-            [9],
-            [6],
-            [],
-            [2, 8],
-            [12],
-            [4, 13],
-            [6, 8],
-            [6, 8, 9],
-            [4],
-            [13],
-            [],
-            [6, 12],
-            [5, 9],
-            [2],
-            [5, 10],
-          ],
-          bottomWalls: [
-            [2, 9],
-            [],
-            [3],
-            [4, 14],
-            [8],
-            [5],
-            [2, 12],
-            [6, 8],
-            [4, 6, 8],
-            [13],
-            [0, 7],
-            [],
-            [11],
-            [5, 12],
-            [9],
-            [2, 8],
-          ],
+      <p style={{ fontStyle: "italic" }}>(The layout of the walls can't be edited, but you can shuffle the four quarters of the board.)</p>
+      <button
+        style={{ margin: "10px" }}
+        onClick={async () => {
+          const { random_board } = await import("ricochet-robots-solver");
+          const board = random_board(Math.floor(Math.random() * 2 ** 32));
+          setWallConfiguration({
+            rightWalls: board.rightWalls,
+            bottomWalls: board.bottomWalls,
+            blockedSquares: board.blockedSquares,
+          });
         }}
-      />
+      >
+        New random board
+      </button>
+      <Board height={16} width={16} wallConfiguration={wallConfiguration} />
     </>
   );
 }
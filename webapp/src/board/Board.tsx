@@ -17,7 +17,11 @@ import {
   targetIcons,
   wildcardTargetIcon,
 } from "./constants";
-import { useSolution } from "../worker/useSolution";
+import { toPuzzle, useSolution } from "../worker/useSolution";
+import {
+  PuzzleGeneratorInput,
+  usePuzzleGenerator,
+} from "../worker/usePuzzleGenerator";
 import { Results } from "./Results";
 
 interface RobotProps {
@@ -64,6 +68,9 @@ interface SquareProps {
   bottomWall: boolean;
   rightWall: boolean;
   leftWall: boolean;
+  blocked: boolean;
+  // moves for the robot shown on the heatmap to get there
+  distance: number | null;
 }
 const Square = ({
   row,
@@ -72,6 +79,8 @@ const Square = ({
   bottomWall,
   rightWall,
   leftWall,
+  blocked,
+  distance,
 }: SquareProps) => {
   const { isOver, setNodeRef } = useDroppable({
     id: `square_${row}_${col}`,
@@ -83,7 +92,14 @@ const Square = ({
     <div
       ref={setNodeRef}
       style={{
-        backgroundColor: isOver ? "grey" : "white",
+        backgroundColor: isOver
+          ? "grey"
+          : blocked
+          ? "#5c5c66"
+          : distance != null
+          ? // the closer, the warmer
+            `hsl(${Math.min(distance, 8) * 30}, 80%, 85%)`
+          : "white",
         width: `${squareSize}px`,
         height: `${squareSize}px`,
         padding: 0,
@@ -97,14 +113,19 @@ const Square = ({
         display: "flex",
         flexDirection: "column",
         justifyContent: "center",
+        alignItems: "center",
+        color: "#5c5c66",
       }}
-    ></div>
+    >
+      {distance}
+    </div>
   );
 };
 interface BoardProps {
   wallConfiguration: {
     bottomWalls: number[][];
     rightWalls: number[][];
+    blockedSquares: { row: number; col: number }[];
   };
   width: number;
   height: number;
@@ -119,14 +140,23 @@ export const Board = ({ width, height, wallConfiguration }: BoardProps) => {
   const [targetPosition, setTargetPosition] = useState({ row: 1, col: 10 });
   const [targetRobot, setTargetRobot] = useState<number | null>(0);
   const [selectedMove, setSelectedMove] = useState<number>(0);
+  const [selectedSolution, setSelectedSolution] = useState<number>(0);
+  // puzzle being generated, if any
+  const [generatorInput, setGeneratorInput] =
+    useState<PuzzleGeneratorInput | null>(null);
+  // robot whose distances to every square are shown on the board
+  const [heatmapRobot, setHeatmapRobot] = useState<number | null>(null);
+  const [distances, setDistances] = useState<(number | undefined)[][] | null>(
+    null
+  );
 
   // automatically reset selected move to 0 whenever the input changes
   // (this is a bit hacky because the component tree will be rendered once with the new position
   // and a selected move that can be > 0, but in practice it doesn't cause problems)
-  useEffect(
-    () => setSelectedMove(0),
-    [robotPositions, targetPosition, targetRobot]
-  );
+  useEffect(() => {
+    setSelectedMove(0);
+    setSelectedSolution(0);
+  }, [robotPositions, targetPosition, targetRobot]);
 
   const solutionInput = useMemo(
     () => ({
@@ -135,6 +165,7 @@ export const Board = ({ width, height, wallConfiguration }: BoardProps) => {
       width,
       rightWalls: wallConfiguration.rightWalls,
       bottomWalls: wallConfiguration.bottomWalls,
+      blockedSquares: wallConfiguration.blockedSquares,
       target: targetPosition,
       targetRobot,
     }),
@@ -147,8 +178,39 @@ export const Board = ({ width, height, wallConfiguration }: BoardProps) => {
       targetRobot,
     ]
   );
+  useEffect(() => {
+    if (heatmapRobot == null) {
+      setDistances(null);
+      return;
+    }
+    let cancelled = false;
+    (async () => {
+      const { distance_map } = await import("ricochet-robots-solver");
+      const distances = distance_map(toPuzzle(solutionInput), heatmapRobot);
+      if (!cancelled) {
+        setDistances(distances);
+      }
+    })();
+    return () => {
+      cancelled = true;
+    };
+  }, [heatmapRobot, solutionInput]);
+  const generatedPuzzle = usePuzzleGenerator(generatorInput);
+  useEffect(() => {
+    if (generatedPuzzle == null) {
+      return;
+    }
+    if (generatedPuzzle.result != null) {
+      setRobotPositions(generatedPuzzle.result.robots);
+      setTargetPosition(generatedPuzzle.result.target.position);
+      setTargetRobot(generatedPuzzle.result.target.robot);
+    }
+    setGeneratorInput(null);
+  }, [generatedPuzzle]);
   const [transition, setTransition] = useState(true);
   const solution = useSolution(solutionInput);
+  const moves =
+    solution.result != null ? solution.result[selectedSolution] ?? [] : null;
 
   const handleDragStart = useCallback(() => {
     // HACK: whenever a robot is moved by drag and drop, we don't want a
@@ -169,9 +231,9 @@ export const Board = ({ width, height, wallConfiguration }: BoardProps) => {
       if (e.active.data.current?.robot != null) {
         const robotId = e.active.data.current.robot.id;
         const currentPositions =
-          selectedMove == 0 || solution.result == null
+          selectedMove == 0 || moves == null
             ? robotPositions
-            : solution.result[selectedMove - 1].robotPositions;
+            : moves[selectedMove - 1].robotPositions;
         if (
           currentPositions.some(
             (value) =>
@@ -187,13 +249,13 @@ export const Board = ({ width, height, wallConfiguration }: BoardProps) => {
         );
       }
       if (e.active.data.current?.target != null) {
-        if (selectedMove > 0 && solution.result != null) {
-          setRobotPositions(solution.result[selectedMove - 1].robotPositions);
+        if (selectedMove > 0 && moves != null) {
+          setRobotPositions(moves[selectedMove - 1].robotPositions);
         }
         setTargetPosition(nextPosition);
       }
     },
-    [solution.result, selectedMove, robotPositions, setRobotPositions]
+    [moves, selectedMove, robotPositions, setRobotPositions]
   );
   return (
     <div
@@ -239,6 +301,10 @@ export const Board = ({ width, height, wallConfiguration }: BoardProps) => {
                       col == width - 1 ||
                       wallConfiguration.rightWalls[row].includes(col)
                     }
+                    blocked={wallConfiguration.blockedSquares.some(
+                      (square) => square.row == row && square.col == col
+                    )}
+                    distance={distances?.[row][col] ?? null}
                   />
                 ))}
               </div>
@@ -263,9 +329,9 @@ export const Board = ({ width, height, wallConfiguration }: BoardProps) => {
             >
               <Target robot={targetRobot} />
             </div>
-            {(selectedMove == 0 || solution.result == null
+            {(selectedMove == 0 || moves == null
               ? robotPositions
-              : solution.result[selectedMove - 1].robotPositions
+              : moves[selectedMove - 1].robotPositions
             ).map((value, index) => (
               <div
                 style={{
@@ -307,23 +373,77 @@ export const Board = ({ width, height, wallConfiguration }: BoardProps) => {
           >
             Change target color
           </button>
+          <button
+            style={{ margin: "10px" }}
+            onClick={() => {
+              setHeatmapRobot((value) =>
+                value == null ? 0 : value == 3 ? null : value + 1
+              );
+            }}
+          >
+            {heatmapRobot == null
+              ? "Show moves to each square"
+              : `Showing moves of ${robotIcons[heatmapRobot]}`}
+          </button>
+          <button
+            style={{ margin: "10px" }}
+            onClick={() => {
+              navigator.clipboard.writeText(
+                JSON.stringify(toPuzzle(solutionInput), null, 2)
+              );
+            }}
+          >
+            Copy puzzle as JSON
+          </button>
+          <button
+            style={{ margin: "10px" }}
+            disabled={generatorInput != null}
+            onClick={() => {
+              // puzzles with the same walls whose shortest solutions have
+              // between 8 and 10 moves
+              setGeneratorInput({
+                puzzle: toPuzzle(solutionInput),
+                seed: Math.floor(Math.random() * 2 ** 32),
+                minMoves: 8,
+                maxMoves: 10,
+              });
+            }}
+          >
+            {generatorInput != null ? "Generating..." : "Give me a hard puzzle"}
+          </button>
         </div>
       </div>
       <div style={{ padding: "0em 1em", minWidth: "20em" }}>
         <p>
           Computation time:{" "}
           {(Math.round(solution.elapsedMilliseconds / 10) / 100).toString()}s
+          {solution.stats != null
+            ? ` (${solution.stats.exploredPositions.toLocaleString()} positions explored)`
+            : null}
         </p>
-        {solution.result != null ? (
+        {solution.limit != null ? (
+          <p>The puzzle is too hard, the solver gave up!</p>
+        ) : solution.result != null && moves != null ? (
           <Results
-            moves={solution.result}
+            moves={moves}
+            solutionCount={solution.result.length}
+            selectedSolution={selectedSolution}
+            setSelectedSolution={setSelectedSolution}
             selectedMove={selectedMove}
             setSelectedMove={setSelectedMove}
             setTransition={setTransition}
           />
+        ) : solution.progress != null && solution.error == null ? (
+          <p>
+            Looking for solutions of {solution.progress.depth} moves,{" "}
+            {solution.progress.exploredPositions.toLocaleString()} positions
+            explored so far...
+          </p>
         ) : null}
         {solution.error != null
-          ? `Error: "${solution.error.toString()}"\n${solution.error.stack}`
+          ? solution.error instanceof Error
+            ? `Error: "${solution.error.toString()}"\n${solution.error.stack}`
+            : `Error: "${solution.error.message}" (${solution.error.code})`
           : null}
       </div>
     </div>
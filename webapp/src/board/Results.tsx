@@ -3,6 +3,9 @@ import { arrowIcons, robotIcons } from "./constants";
 
 interface ResultsProps {
   moves: { robot: number; direction: number }[];
+  solutionCount: number;
+  selectedSolution: number;
+  setSelectedSolution: Dispatch<SetStateAction<number>>;
   selectedMove: number;
   setSelectedMove: Dispatch<SetStateAction<number>>;
   setTransition: Dispatch<SetStateAction<boolean>>;
@@ -10,6 +13,9 @@ interface ResultsProps {
 
 export const Results = ({
   moves,
+  solutionCount,
+  selectedSolution,
+  setSelectedSolution,
   selectedMove,
   setSelectedMove,
   setTransition,
@@ -54,6 +60,33 @@ export const Results = ({
   return (
     <div>
       <p>Found solution in {moves.length} moves!</p>
+      {solutionCount > 1 ? (
+        <div>
+          <button
+            style={{ margin: "0em 1em" }}
+            onClick={() => {
+              setTransition(false);
+              setSelectedMove(0);
+              setSelectedSolution(
+                (current) => (current + solutionCount - 1) % solutionCount
+              );
+            }}
+          >
+            ◀️
+          </button>
+          Solution {selectedSolution + 1} of {solutionCount}
+          <button
+            style={{ margin: "0em 1em" }}
+            onClick={() => {
+              setTransition(false);
+              setSelectedMove(0);
+              setSelectedSolution((current) => (current + 1) % solutionCount);
+            }}
+          >
+            ▶️
+          </button>
+        </div>
+      ) : null}
       <div>
         {prependedMoves.map((value, index) => (
           <div
@@ -0,0 +1,39 @@
+import { useCallback } from "react";
+import { useWorkerResult } from "./useWorkerResult";
+import { toPuzzle } from "./useSolution";
+
+// See https://v3.vitejs.dev/guide/features.html#import-with-query-suffixes
+import PuzzleGeneratorWorker from "./usePuzzleGenerator_worker?worker";
+
+export interface PuzzleGeneratorInput {
+  // the walls and the number of robots of this puzzle are kept
+  puzzle: ReturnType<typeof toPuzzle>;
+  seed: number;
+  // bounds of the number of moves of the shortest solutions
+  minMoves: number;
+  maxMoves: number;
+}
+
+// Puzzle in the JSON format shared with the Rust library
+export interface GeneratedPuzzle {
+  robots: { row: number; col: number }[];
+  target: { robot: number | null; position: { row: number; col: number } };
+}
+
+export interface PuzzleGeneratorHookResult {
+  // null if no puzzle with that many moves was found
+  result: GeneratedPuzzle | null;
+  error: Error | null;
+}
+
+// Generates a puzzle in a web worker, since it can take a few seconds. Returns
+// null while the input is null or the puzzle is being generated.
+export const usePuzzleGenerator = (
+  input: PuzzleGeneratorInput | null
+): PuzzleGeneratorHookResult | null => {
+  const createWorker = useCallback(() => new PuzzleGeneratorWorker(), []);
+  return useWorkerResult<PuzzleGeneratorInput, GeneratedPuzzle>(
+    createWorker,
+    input
+  );
+};
@@ -0,0 +1,20 @@
+import { PuzzleGeneratorInput } from "./usePuzzleGenerator";
+
+self.onmessage = async (ev: MessageEvent) => {
+  console.log("worker received event", ev);
+  try {
+    const { generate_puzzle } = await import("ricochet-robots-solver");
+    const input: PuzzleGeneratorInput = ev.data;
+    const result = generate_puzzle(
+      input.puzzle,
+      input.seed,
+      input.minMoves,
+      input.maxMoves
+    );
+    console.log("worker computed result", result);
+    postMessage({ result: result ?? null, error: null });
+  } catch (error) {
+    console.log("worker crashed", error);
+    postMessage({ result: null, error });
+  }
+};
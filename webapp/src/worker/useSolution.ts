@@ -1,20 +1,53 @@
-import { useCallback } from "react";
+import { useCallback, useEffect, useState } from "react";
 import { useWorkerResult } from "./useWorkerResult";
 import { useElapsedTime } from "./useElapsedTime";
 
 // See https://v3.vitejs.dev/guide/features.html#import-with-query-suffixes
 import SolutionWorker from "./useSolution_worker?worker";
 
+// Errors thrown by the solver for invalid inputs (see `SolveError` in the Rust library)
+export interface SolveError {
+  code: string;
+  message: string;
+}
+
+export interface Move {
+  robot: number;
+  direction: number;
+  robotPositions: { row: number; col: number }[];
+  // squares where the robot bounced off a mirror, then where it stopped
+  path: { row: number; col: number }[];
+}
+
+// Reported by the solver every few thousand explored positions
+export interface SolveProgress {
+  // number of moves of the solutions being looked for
+  depth: number;
+  exploredPositions: number;
+  queuedSequences: number;
+  elapsedMilliseconds: number;
+}
+
+// Statistics about a finished search (see `SolveStats` in the Rust library)
+export interface SolveStats {
+  exploredPositions: number;
+  generatedSequences: number;
+  maxQueuedSequences: number;
+  depth: number;
+  durationMilliseconds: number;
+}
+
 export interface SolutionHookResult {
-  result:
-    | {
-        robot: number;
-        direction: number;
-        robotPositions: { row: number; col: number }[];
-      }[]
-    | null;
-  error: Error | null;
+  // every shortest solution, the ones switching robots the least first (empty
+  // if there is no solution)
+  result: Move[][] | null;
+  // set when the solver gave up without finding a solution
+  limit: "maxMoves" | "maxExploredPositions" | "cancelled" | null;
+  error: SolveError | Error | null;
   elapsedMilliseconds: number;
+  stats: SolveStats | null;
+  // latest progress of the running search (null until the first report)
+  progress: SolveProgress | null;
 }
 
 export interface UseSolutionHookInput {
@@ -23,27 +56,54 @@ export interface UseSolutionHookInput {
   width: number;
   rightWalls: number[][];
   bottomWalls: number[][];
+  blockedSquares: { row: number; col: number }[];
   target: { row: number; col: number };
   targetRobot: number | null;
+  // how solutions of the same length are ranked (see `CostModel` in the Rust
+  // library), fewest robot changes first by default
+  costModel?: {
+    robotChange?: number;
+    newRobot?: number;
+    otherRobot?: number;
+    bounceBack?: number;
+  };
 }
 
+// Puzzle in the JSON format shared with the Rust library (see `Puzzle` in
+// `solver_library/src/solver/puzzle.rs`), so that it can be replayed natively
+export const toPuzzle = (input: UseSolutionHookInput) => ({
+  version: 1,
+  height: input.height,
+  width: input.width,
+  rightWalls: input.rightWalls,
+  bottomWalls: input.bottomWalls,
+  blockedSquares: input.blockedSquares,
+  robots: input.robotPositions,
+  target: { robot: input.targetRobot, position: input.target },
+});
+
 export const useSolution = (
   input: UseSolutionHookInput
 ): SolutionHookResult => {
   const createWorker = useCallback(() => new SolutionWorker(), []);
+  const [progress, setProgress] = useState<SolveProgress | null>(null);
+  useEffect(() => setProgress(null), [input]);
   const result = useWorkerResult<
     UseSolutionHookInput,
     {
-      robot: number;
-      direction: number;
-      robotPositions: { row: number; col: number }[];
-    }[]
-  >(createWorker, input);
+      solutions: Move[][];
+      limit: SolutionHookResult["limit"];
+      stats: SolveStats;
+    }
+  >(createWorker, input, setProgress);
   const elapsedMilliseconds = useElapsedTime(input, result != null, 500);
   const solutionResult = {
-    result: result != null ? result.result : null,
+    result: result?.result != null ? result.result.solutions : null,
+    limit: result?.result != null ? result.result.limit : null,
     error: result != null ? result.error : null,
     elapsedMilliseconds,
+    stats: result?.result != null ? result.result.stats : null,
+    progress,
   };
   return solutionResult;
 };
@@ -1,18 +1,20 @@
-import { UseSolutionHookInput } from "./useSolution";
+import { SolveProgress, UseSolutionHookInput, toPuzzle } from "./useSolution";
+
+// There can be thousands of shortest solutions, only the best ones are shown
+const MAX_SOLUTIONS = 20;
+// Give up on puzzles that would take too long (or too much memory) to solve
+const MAX_EXPLORED_POSITIONS = 5_000_000;
 
 self.onmessage = async (ev: MessageEvent) => {
   console.log("worker received event", ev);
   try {
-    const { solve, Position } = await import("ricochet-robots-solver");
+    const { solve_puzzle_all } = await import("ricochet-robots-solver");
     const input: UseSolutionHookInput = ev.data;
-    const result = solve(
-      input.robotPositions.map((value) => new Position(value.row, value.col)),
-      input.height,
-      input.width,
-      { rightWalls: input.rightWalls, bottomWalls: input.bottomWalls },
-      new Position(input.target.row, input.target.col),
-      input.targetRobot != null ? input.targetRobot : undefined
-    );
+    const result = solve_puzzle_all(toPuzzle(input), {
+      maxSolutions: MAX_SOLUTIONS,
+      maxExploredPositions: MAX_EXPLORED_POSITIONS,
+      costModel: input.costModel,
+    }, (progress: SolveProgress) => postMessage({ progress }));
     console.log("worker computed result", result);
     postMessage({
       result,
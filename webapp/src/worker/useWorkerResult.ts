@@ -6,10 +6,13 @@ export interface WorkerResult<R> {
   error: Error | null;
 }
 
-// This hook is a toy example for delegating an expensive computation to a web worker
+// This hook is a toy example for delegating an expensive computation to a web worker.
+// Workers can also post `{ progress }` messages before the result, which are
+// handed over to `onProgress`. No worker is started while the input is null.
 export const useWorkerResult = <P, R>(
   createWorker: () => Worker,
-  input: P
+  input: P | null,
+  onProgress?: (progress: any) => void
 ): WorkerResult<R> | null => {
   const [result, setResult] = useState<WorkerResult<R> | null>(null);
 
@@ -17,12 +20,19 @@ export const useWorkerResult = <P, R>(
   useEffect(() => {
     // Reset result
     setResult(null);
+    if (input == null) {
+      return;
+    }
 
     // Start a web worker
     const worker = createWorker();
 
     // Be prepared to receive the result
     worker.onmessage = (ev) => {
+      if (ev.data.progress !== undefined) {
+        onProgress?.(ev.data.progress);
+        return;
+      }
       console.log(ev);
       setResult(ev.data);
       worker.terminate();
@@ -34,7 +44,7 @@ export const useWorkerResult = <P, R>(
     return () => {
       worker.terminate();
     };
-  }, [createWorker, input]);
+  }, [createWorker, input, onProgress]);
 
   return result;
 };